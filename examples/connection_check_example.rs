@@ -5,7 +5,8 @@ use nes_rs::prelude::*;
 async fn main(){
     simple_logger::init_with_level(log::Level::Trace).expect("Simple_logger should not fail!");
     log::info!("This example shows the is_connected function.");
-    let runtime = NebulaStreamRuntime::new("localhost", 8081);
+    let runtime = NebulaStreamRuntime::new("localhost", 8081)
+        .expect("Default HTTP client should build!");
     let is_connected = runtime.check_connection().await;
     log::info!("NebulaStream is connected: {is_connected}");
 }
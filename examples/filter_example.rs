@@ -4,7 +4,8 @@ use nes_rust_client::prelude::*;
 #[tokio::main]
 async fn main() {
     simple_logger::init_with_level(log::Level::Trace).expect("Init simple_logger should not fail!");
-    let runtime = NebulaStreamRuntime::new("localhost", 8081);
+    let runtime = NebulaStreamRuntime::new("localhost", 8081)
+        .expect("Default HTTP client should build!");
     let query = QueryBuilder::from_source("wind_turbines".to_string())
         .filter(
             EB::field("metadata_generated")
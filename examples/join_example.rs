@@ -8,7 +8,8 @@ extern crate nes_rust_client as nes_rs;
 
 #[tokio::main]
 async fn main() {
-    let runtime = NebulaStreamRuntime::new("localhost".to_string(), 8081);
+    let runtime = NebulaStreamRuntime::new("localhost".to_string(), 8081)
+        .expect("Default HTTP client should build!");
 
     let query_to_join = QueryBuilder::from_source("wind_turbines");
     let query = QueryBuilder::from_source("wind_turbines")
@@ -18,7 +19,7 @@ async fn main() {
         .window(WindowDescriptor::TumblingWindow {
             duration: Duration::from_seconds(10),
             time_character: TimeCharacteristic::EventTime {
-                field_name: todo!(),
+                field_name: "features_properties_updated".to_string(),
                 unit: TimeUnit::Milliseconds,
             },
         })
@@ -30,5 +31,4 @@ async fn main() {
         Ok(query_id) => log::info!("Started Execution of query with id: {query_id}"),
         Err(err) => log::error!("Failed to execute query: {}", err),
     }
-    //TODO
 }
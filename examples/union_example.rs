@@ -3,7 +3,8 @@ use nes_rust_client::prelude::*;
 #[tokio::main]
 async fn main() {
     simple_logger::init_with_level(log::Level::Trace).expect("Init simple_logger should not fail!");
-    let runtime = NebulaStreamRuntime::new("localhost".to_string(), 8081);
+    let runtime = NebulaStreamRuntime::new("localhost".to_string(), 8081)
+        .expect("Default HTTP client should build!");
 
     let query0 = QueryBuilder::from_source("wind_turbines");
     let query1 = QueryBuilder::from_source("wind_turbines")
@@ -3,7 +3,8 @@ use nes_rust_client::query::time::{Duration, TimeCharacteristic, TimeUnit};
 
 #[tokio::main]
 async fn main() {
-    let runtime = NebulaStreamRuntime::new("localhost".to_string(), 8081);
+    let runtime = NebulaStreamRuntime::new("localhost".to_string(), 8081)
+        .expect("Default HTTP client should build!");
 
     // Build a query to be send
     // The Cpp Query translates into the following query
@@ -0,0 +1,242 @@
+use std::{collections::HashMap, error::Error, path::Path, path::PathBuf, process::ExitCode};
+
+use clap::{Parser, Subcommand};
+use nes_rust_client::prelude::*;
+use nes_rust_client::query::stringify::stringify_query;
+use serde_json::json;
+
+/// Submit and manage queries of a NebulaStream coordinator.
+#[derive(Parser)]
+#[command(name = "nes-cli", version)]
+struct Cli {
+    /// Host of the coordinator.
+    #[arg(long, global = true, default_value = "localhost")]
+    host: String,
+    /// REST port of the coordinator.
+    #[arg(long, global = true, default_value_t = 8081)]
+    port: i32,
+    /// Print results as JSON.
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check if the coordinator is reachable.
+    Check,
+    /// List all registered queries with their state.
+    Queries,
+    /// Submit a query from a file. Files ending in `.ron` are read as RON, all other files in the
+    /// textual format printed by `print`.
+    Submit {
+        file: PathBuf,
+        /// Placement strategy, e.g. BottomUp, TopDown, ILP, or ELEGANT_BALANCED.
+        #[arg(long, default_value = "BottomUp")]
+        placement: PlacementStrategy,
+        /// Additional entry of the request context, can be repeated.
+        #[arg(long = "context", value_name = "KEY=VALUE", value_parser = parse_context_entry)]
+        context: Vec<(String, String)>,
+    },
+    /// Stop a running query.
+    Stop { query_id: i64 },
+    /// List all logical sources.
+    Sources,
+    /// Pretty print a query file.
+    Print { file: PathBuf },
+}
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match NebulaStreamRuntime::new(&cli.host, cli.port) {
+        Ok(runtime) => run(&runtime, &cli).await,
+        Err(err) => Err(err.into()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            if cli.json {
+                eprintln!("{}", json!({ "error": err.to_string() }));
+            } else {
+                eprintln!("{err}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(runtime: &NebulaStreamRuntime, cli: &Cli) -> CliResult {
+    match &cli.command {
+        Command::Check => check(runtime, cli.json).await,
+        Command::Queries => queries(runtime, cli.json).await,
+        Command::Submit {
+            file,
+            placement,
+            context,
+        } => submit(runtime, file, *placement, context, cli.json).await,
+        Command::Stop { query_id } => stop(runtime, *query_id, cli.json).await,
+        Command::Sources => sources(runtime, cli.json).await,
+        Command::Print { file } => print(file, cli.json),
+    }
+}
+
+fn parse_context_entry(entry: &str) -> Result<(String, String), String> {
+    entry
+        .split_once('=')
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| format!("Expected KEY=VALUE but got {entry}"))
+}
+
+fn load_query(file: &Path) -> Result<Query, Box<dyn Error>> {
+    let source = std::fs::read_to_string(file)
+        .map_err(|err| format!("Unable to read {}: {err}", file.display()))?;
+    if file.extension().is_some_and(|extension| extension == "ron") {
+        return Ok(ron::from_str(&source)?);
+    }
+    parse_query(&source).map_err(|err| err.report(&source).into())
+}
+
+async fn check(runtime: &NebulaStreamRuntime, as_json: bool) -> CliResult {
+    let is_connected = runtime.check_connection().await;
+    if as_json {
+        println!("{}", json!({ "connected": is_connected }));
+    } else {
+        println!("Connected: {is_connected}");
+    }
+    if !is_connected {
+        return Err("Coordinator is not reachable.".into());
+    }
+    Ok(())
+}
+
+async fn queries(runtime: &NebulaStreamRuntime, as_json: bool) -> CliResult {
+    let queries = runtime.registered_queries().await?;
+    if as_json {
+        let queries: Vec<_> = queries
+            .iter()
+            .map(|query| {
+                json!({
+                    "queryId": query.query_id,
+                    "queryStatus": query.query_status.to_string(),
+                    "queryString": query.query_string,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::Value::Array(queries));
+        return Ok(());
+    }
+    for query in queries {
+        println!(
+            "{:>6}  {:<24}  {}",
+            query.query_id,
+            query.query_status.to_string(),
+            query.query_string
+        );
+    }
+    Ok(())
+}
+
+async fn submit(
+    runtime: &NebulaStreamRuntime,
+    file: &Path,
+    placement: PlacementStrategy,
+    context: &[(String, String)],
+    as_json: bool,
+) -> CliResult {
+    let query = load_query(file)?;
+    let context: HashMap<_, _> = context.iter().cloned().collect();
+    let query_id = runtime
+        .execute_query_with_context(&query, placement, &context)
+        .await?;
+    if as_json {
+        println!("{}", json!({ "queryId": query_id }));
+    } else {
+        println!("Submitted query with id {query_id}.");
+    }
+    Ok(())
+}
+
+async fn stop(runtime: &NebulaStreamRuntime, query_id: i64, as_json: bool) -> CliResult {
+    runtime.stop_query(query_id).await?;
+    if as_json {
+        println!("{}", json!({ "queryId": query_id, "stopped": true }));
+    } else {
+        println!("Stopped query with id {query_id}.");
+    }
+    Ok(())
+}
+
+async fn sources(runtime: &NebulaStreamRuntime, as_json: bool) -> CliResult {
+    let sources = runtime.logical_sources().await?;
+    if as_json {
+        println!("{}", json!(sources));
+        return Ok(());
+    }
+    for source in sources {
+        println!("{source}");
+    }
+    Ok(())
+}
+
+fn print(file: &Path, as_json: bool) -> CliResult {
+    let query = load_query(file)?;
+    let query_string = stringify_query(&query);
+    if as_json {
+        println!("{}", json!({ "query": query_string }));
+    } else {
+        println!("{query_string}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use nes_rust_client::prelude::*;
+    use nes_rust_client::query::stringify::stringify_query;
+
+    use super::{load_query, parse_context_entry};
+
+    fn query_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("nes-cli-{}-{name}", std::process::id()));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_context_entry() {
+        assert_eq!(
+            ("key".to_string(), "a=b".to_string()),
+            parse_context_entry("key=a=b").unwrap()
+        );
+        assert_eq!(
+            ("key".to_string(), String::new()),
+            parse_context_entry("key=").unwrap()
+        );
+        assert!(parse_context_entry("key").is_err());
+    }
+
+    #[test]
+    fn test_load_query() {
+        let query = QueryBuilder::from_source("test").sink(Sink::Print);
+        let text = query_file("query.nes", &stringify_query(&query));
+        let ron = query_file("query.ron", &ron::to_string(&query).unwrap());
+        for path in [&text, &ron] {
+            let loaded = load_query(path).unwrap();
+            assert_eq!(stringify_query(&query), stringify_query(&loaded));
+        }
+        fs::write(&text, "logical_source(\"test\").sink(").unwrap();
+        let err = load_query(&text).unwrap_err();
+        assert!(err.to_string().contains("expected"));
+        fs::write(&ron, "Query(").unwrap();
+        assert!(load_query(&ron).is_err());
+        fs::remove_file(text).unwrap();
+        fs::remove_file(ron).unwrap();
+        assert!(load_query(&PathBuf::from("missing.ron")).is_err());
+    }
+}
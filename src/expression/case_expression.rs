@@ -0,0 +1,40 @@
+use nes_types::NesType;
+use serde::{Deserialize, Serialize};
+
+use super::expression::RawExpr;
+
+/// A conditional expression. The result is the `value` of the first branch whose `condition`
+/// evaluates to true, or `default` if no condition holds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CaseExpr {
+    pub branches: Vec<WhenBranch>,
+    pub default: Box<RawExpr>,
+    pub data_type: NesType,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WhenBranch {
+    pub condition: RawExpr,
+    pub value: RawExpr,
+}
+
+impl CaseExpr {
+    pub fn data_type(&self) -> NesType {
+        self.data_type
+    }
+
+    /// Returns the conditions, values and the default of this expression.
+    pub fn children(&self) -> Vec<&RawExpr> {
+        self.branches
+            .iter()
+            .flat_map(|branch| [&branch.condition, &branch.value])
+            .chain(std::iter::once(self.default.as_ref()))
+            .collect()
+    }
+
+    /// Returns the common type of `values` or None if they can not be resolved to a common type.
+    pub fn resolve_type<'a>(mut values: impl Iterator<Item = &'a RawExpr>) -> Option<NesType> {
+        let first = values.next()?.data_type();
+        values.try_fold(first, |acc, value| NesType::try_resolve(acc, value.data_type()))
+    }
+}
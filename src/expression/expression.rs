@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     binary_expression::{BinaryExpr, BinaryOp},
+    case_expression::CaseExpr,
     field::Field,
     literal::Literal,
     unary_expression::{UnaryExpr, UnaryOp},
@@ -14,6 +15,7 @@ pub enum RawExpr {
     Field(Field),
     Unary(UnaryExpr),
     Binary(BinaryExpr),
+    Case(CaseExpr),
 }
 
 impl RawExpr {
@@ -40,6 +42,7 @@ impl RawExpr {
             RawExpr::Field(field) => field.data_type(),
             RawExpr::Unary(expr) => expr.data_type(),
             RawExpr::Binary(expr) => expr.data_type(),
+            RawExpr::Case(expr) => expr.data_type(),
         }
     }
 
@@ -60,7 +63,7 @@ impl RawExpr {
     pub fn is_leaf(&self) -> bool {
         match self {
             RawExpr::Literal(_) | RawExpr::Field(_) => true,
-            RawExpr::Unary(_) | RawExpr::Binary(_) => false,
+            RawExpr::Unary(_) | RawExpr::Binary(_) | RawExpr::Case(_) => false,
         }
     }
 
@@ -73,6 +76,10 @@ impl RawExpr {
                 lhs.leafs_recursive(vec);
                 rhs.leafs_recursive(vec);
             }
+            RawExpr::Case(expr) => expr
+                .children()
+                .into_iter()
+                .for_each(|child| child.leafs_recursive(vec)),
         }
     }
 
@@ -99,6 +106,14 @@ impl RawExpr {
                     return true;
                 }
             }
+            RawExpr::Case(expr)
+                if expr
+                    .children()
+                    .into_iter()
+                    .any(|child| child.traverse_and_check(condition)) =>
+            {
+                return true;
+            }
             _ => (),
         }
 
@@ -120,6 +135,15 @@ impl RawExpr {
                 lhs.leafs_parents_recursive(vec);
                 rhs.leafs_parents_recursive(vec);
             }
+            RawExpr::Case(expr) => {
+                let children = expr.children();
+                if children.iter().any(|child| child.is_leaf()) {
+                    vec.push(self.clone());
+                }
+                children
+                    .into_iter()
+                    .for_each(|child| child.leafs_parents_recursive(vec));
+            }
             _ => (),
         }
     }
@@ -155,6 +179,12 @@ impl From<UnaryExpr> for RawExpr {
     }
 }
 
+impl From<CaseExpr> for RawExpr {
+    fn from(expr: CaseExpr) -> Self {
+        RawExpr::Case(expr)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::fmt::Display;
@@ -4,8 +4,9 @@ use nes_types::NesType;
 
 use super::{
     binary_expression::{BinaryExpr, BinaryOp},
+    case_expression::{CaseExpr, WhenBranch},
     expression::RawExpr,
-    expression_builder_macros::{boolean_operator, cmp_operator},
+    expression_builder_macros::{boolean_operator, cmp_operator, ops_impl, unary_operator},
     field::Field,
     literal::Literal,
     unary_expression::{UnaryExpr, UnaryOp},
@@ -53,6 +54,16 @@ impl ExprBuilder {
         }
     }
 
+    /// Starts a conditional expression that evaluates to `value` if `condition` holds. Further
+    /// branches and the default value are added through the returned `CaseBuilder`.
+    pub fn when(condition: Self, value: Self) -> CaseBuilder {
+        CaseBuilder {
+            branches: Vec::new(),
+            error: None,
+        }
+        .when(condition, value)
+    }
+
     // unary logical
     pub fn not(mut self) -> Self {
         let data_type = match self.expr.data_type() {
@@ -79,22 +90,33 @@ impl ExprBuilder {
     cmp_operator!(less_than, BinaryOp::Less);
     cmp_operator!(less_equals, BinaryOp::LessEquals);
 
-    // binary arith
-    pub fn div(mut self, other: Self) -> Self {
-        let data_type =
-            match nes_types::NesType::try_resolve(self.expr.data_type(), other.expr.data_type()) {
-                Some(nes_types::NesType::Int(t)) => nes_types::NesType::Int(t),
-                Some(nes_types::NesType::Float(t)) => nes_types::NesType::Float(t),
-                Some(nes_types::NesType::Undefined) => nes_types::NesType::Undefined,
-                _ => {
-                    self.error = Some(ExprBuildError {});
-                    nes_types::NesType::Undefined
-                }
-            };
+    // unary arith
+    unary_operator!(abs, UnaryOp::Absolute);
+    unary_operator!(ceil, UnaryOp::Ceil);
+    unary_operator!(floor, UnaryOp::Floor);
+    unary_operator!(round, UnaryOp::Round);
+    unary_operator!(sqrt, UnaryOp::Sqrt);
+    unary_operator!(exp, UnaryOp::Exp);
+
+    // binary arith, `+`, `-`, `*`, `/` and `%` are implemented through `std::ops`
+    pub fn pow(self, other: Self) -> Self {
+        self.arith(other, BinaryOp::Power)
+    }
+
+    fn arith(mut self, other: Self, operator: BinaryOp) -> Self {
+        let data_type = match NesType::try_resolve(self.expr.data_type(), other.expr.data_type()) {
+            Some(NesType::Int(t)) => NesType::Int(t),
+            Some(NesType::Float(t)) => NesType::Float(t),
+            Some(NesType::Undefined) => NesType::Undefined,
+            _ => {
+                self.error = Some(ExprBuildError {});
+                NesType::Undefined
+            }
+        };
         self.expr = RawExpr::Binary(BinaryExpr {
             lhs: Box::new(self.expr),
             rhs: Box::new(other.expr),
-            operator: BinaryOp::Divide,
+            operator,
             data_type,
         });
         self
@@ -122,6 +144,60 @@ impl ExprBuilder {
     }
 }
 
+/// Builds a CASE/WHEN expression. Created by `ExprBuilder::when`.
+pub struct CaseBuilder {
+    branches: Vec<WhenBranch>,
+    error: Option<ExprBuildError>,
+}
+
+impl CaseBuilder {
+    pub fn when(mut self, condition: ExprBuilder, value: ExprBuilder) -> Self {
+        if !matches!(
+            condition.expr.data_type(),
+            NesType::Bool | NesType::Undefined
+        ) {
+            self.error = Some(ExprBuildError {});
+        }
+        if let Some(err) = condition.error.or(value.error) {
+            self.error = Some(err);
+        }
+        self.branches.push(WhenBranch {
+            condition: condition.expr,
+            value: value.expr,
+        });
+        self
+    }
+
+    /// Completes the expression with the value that is used if no condition holds. All values
+    /// must resolve to a common type.
+    pub fn otherwise(self, default: ExprBuilder) -> ExprBuilder {
+        let mut error = self.error.or(default.error);
+        let values = self
+            .branches
+            .iter()
+            .map(|branch| &branch.value)
+            .chain(std::iter::once(&default.expr));
+        let data_type = CaseExpr::resolve_type(values).unwrap_or_else(|| {
+            error = Some(ExprBuildError {});
+            NesType::Undefined
+        });
+        ExprBuilder {
+            expr: RawExpr::Case(CaseExpr {
+                branches: self.branches,
+                default: Box::new(default.expr),
+                data_type,
+            }),
+            error,
+        }
+    }
+}
+
+ops_impl!(Add, add, BinaryOp::Add);
+ops_impl!(Sub, sub, BinaryOp::Sub);
+ops_impl!(Mul, mul, BinaryOp::Multiply);
+ops_impl!(Div, div, BinaryOp::Divide);
+ops_impl!(Rem, rem, BinaryOp::Remainder);
+
 #[cfg(test)]
 mod expression_builder_test {
     use crate::expression::binary_expression::BinaryExpr;
@@ -249,4 +325,86 @@ mod expression_builder_test {
             .build_logical()
             .is_ok());
     }
+
+    #[test]
+    fn arith_test() {
+        let expr = (EB::literal(1) + EB::literal(2)).build_arith().unwrap();
+        let expected = ArithmeticExpr(RE::Binary(BinaryExpr {
+            lhs: Box::new(RE::Literal(1.into())),
+            rhs: Box::new(RE::Literal(2.into())),
+            operator: BinaryOp::Add,
+            data_type: NesType::Int(nes_types::IntType::Signed32),
+        }));
+        assert_eq!(expected, expr);
+        assert!((EB::literal(1) - EB::field("value")).build_arith().is_ok());
+        assert!((EB::literal(1) * EB::literal(true)).build_arith().is_err());
+        assert!((EB::literal(1) % EB::literal(2)).build_logical().is_err());
+        assert!(EB::literal(true).pow(EB::literal(false)).build_arith().is_err());
+    }
+
+    #[test]
+    fn arith_ops_test() {
+        use std::ops::{Add, Mul};
+
+        let expr = (EB::field("value") * EB::literal(2) + EB::literal(1))
+            .build_arith()
+            .unwrap();
+        let expected = EB::field("value")
+            .mul(EB::literal(2))
+            .add(EB::literal(1))
+            .build_arith()
+            .unwrap();
+        assert_eq!(expected, expr);
+        let expr = (EB::field("value") - EB::literal(1)) / EB::literal(2) % EB::literal(3);
+        let RE::Binary(BinaryExpr { operator, .. }) = expr.build_arith().unwrap().0 else {
+            panic!("Expected binary expression");
+        };
+        assert_eq!(BinaryOp::Remainder, operator);
+    }
+
+    #[test]
+    fn math_test() {
+        let expr = EB::literal(4).sqrt().build_arith().unwrap();
+        let expected = ArithmeticExpr(RE::Unary(UnaryExpr {
+            expr: Box::new(RE::Literal(4.into())),
+            operator: UnaryOp::Sqrt,
+            data_type: NesType::Float(nes_types::FloatType::Bit64),
+        }));
+        assert_eq!(expected, expr);
+        let expr = EB::literal(-4).abs().build_arith().unwrap();
+        assert_eq!(NesType::Int(nes_types::IntType::Signed32), expr.0.data_type());
+        assert!(EB::field("value").exp().round().build_arith().is_ok());
+        assert!(EB::field("value").ceil().floor().build_arith().is_ok());
+        assert!(EB::literal(true).ceil().build_arith().is_err());
+        assert!(EB::literal(true).sqrt().build_arith().is_err());
+    }
+
+    #[test]
+    fn case_test() {
+        let expr = EB::when(
+            EB::field("temperature").greater_than(EB::literal(100)),
+            EB::literal(1),
+        )
+        .otherwise(EB::literal(0))
+        .build_arith()
+        .unwrap();
+        let RE::Case(case) = expr.0 else {
+            panic!("Expected case expression");
+        };
+        assert_eq!(1, case.branches.len());
+        assert_eq!(NesType::Int(nes_types::IntType::Signed32), case.data_type);
+        assert!(EB::when(EB::literal(true), EB::literal(1))
+            .when(EB::literal(false), EB::literal(2))
+            .otherwise(EB::literal(true))
+            .build_arith()
+            .is_err());
+        assert!(EB::when(EB::literal(1), EB::literal(1))
+            .otherwise(EB::literal(0))
+            .build_arith()
+            .is_err());
+        assert!(EB::when(EB::literal(true), EB::literal(false))
+            .otherwise(EB::literal(true))
+            .build_logical()
+            .is_ok());
+    }
 }
@@ -42,5 +42,39 @@ macro_rules! boolean_operator {
     };
 }
 
+macro_rules! unary_operator {
+    ($name:ident, $operator:expr) => {
+        pub fn $name(mut self) -> Self {
+            let data_type = match $operator.result_type(self.expr.data_type()) {
+                Some(data_type) => data_type,
+                None => {
+                    self.error = Some(ExprBuildError {});
+                    nes_types::NesType::Undefined
+                }
+            };
+            self.expr = RawExpr::Unary(UnaryExpr {
+                expr: Box::new(self.expr),
+                operator: $operator,
+                data_type,
+            });
+            self
+        }
+    };
+}
+
+macro_rules! ops_impl {
+    ($trait:ident, $name:ident, $operator:expr) => {
+        impl std::ops::$trait for ExprBuilder {
+            type Output = ExprBuilder;
+
+            fn $name(self, other: Self) -> Self::Output {
+                self.arith(other, $operator)
+            }
+        }
+    };
+}
+
 pub(super) use boolean_operator;
 pub(super) use cmp_operator;
+pub(super) use ops_impl;
+pub(super) use unary_operator;
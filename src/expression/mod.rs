@@ -1,4 +1,5 @@
 pub mod binary_expression;
+pub mod case_expression;
 pub mod expression;
 pub mod expression_builder;
 mod expression_builder_macros;
@@ -7,7 +8,7 @@ pub mod literal;
 pub mod unary_expression;
 
 use expression::RawExpr;
-pub use expression_builder::{ExprBuildError, ExprBuilder};
+pub use expression_builder::{CaseBuilder, ExprBuildError, ExprBuilder};
 pub use field::Field;
 use serde::{Deserialize, Serialize};
 
@@ -1,4 +1,4 @@
-use nes_types::NesType;
+use nes_types::{FloatType, NesType};
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
@@ -19,15 +19,44 @@ impl UnaryExpr {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
 pub enum UnaryOp {
+    // Logical
     Negate,
+    // Arithmetic
     Absolute,
+    Ceil,
+    Floor,
+    Round,
+    Sqrt,
+    Exp,
 }
 
 impl UnaryOp {
     pub const fn is_logical(&self) -> bool {
         match self {
             UnaryOp::Negate => true,
-            UnaryOp::Absolute => false,
+            UnaryOp::Absolute
+            | UnaryOp::Ceil
+            | UnaryOp::Floor
+            | UnaryOp::Round
+            | UnaryOp::Sqrt
+            | UnaryOp::Exp => false,
+        }
+    }
+
+    /// Returns the type of the result if the operator is applied to an operand of type
+    /// `data_type`, or None if the operator is not defined for this type.
+    pub const fn result_type(&self, data_type: NesType) -> Option<NesType> {
+        match (self, data_type) {
+            (UnaryOp::Negate, NesType::Bool | NesType::Undefined) => Some(NesType::Bool),
+            (
+                UnaryOp::Sqrt | UnaryOp::Exp,
+                NesType::Int(_) | NesType::Float(_) | NesType::Undefined,
+            ) => Some(NesType::Float(FloatType::Bit64)),
+            (
+                UnaryOp::Absolute | UnaryOp::Ceil | UnaryOp::Floor | UnaryOp::Round,
+                NesType::Int(_) | NesType::Float(_) | NesType::Undefined,
+            ) => Some(data_type),
+            _ => None,
         }
     }
 }
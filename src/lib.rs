@@ -3,4 +3,7 @@ pub mod expression;
 pub mod query;
 pub mod serialization;
 pub mod prelude;
+pub mod results;
 
+#[cfg(feature = "mock-coordinator")]
+pub mod testing;
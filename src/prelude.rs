@@ -1,10 +1,24 @@
+pub use crate::runtime::nebula_stream_config::NebulaStreamConfig;
+pub use crate::runtime::nebula_stream_config::RetryPolicy;
 pub use crate::runtime::nebula_stream_runtime::NebulaStreamRuntime;
-pub use crate::runtime::nebula_stream_runtime::PlacementStrategy;
+pub use crate::runtime::placement_strategy::ElegantObjective;
+pub use crate::runtime::placement_strategy::PlacementStrategy;
 pub use crate::runtime::query_state::QueryState;
 pub use crate::runtime::query_state::QueryStateParseError;
+pub use crate::runtime::runtime_error::RuntimeError;
 pub use crate::query::QueryBuilder;
 pub use crate::query::Query;
+pub use crate::query::sink::MqttQualityOfService;
+pub use crate::query::sink::MqttTimeUnit;
+pub use crate::query::sink::OpcIdentifierType;
+pub use crate::query::sink::Password;
 pub use crate::query::sink::Sink;
+pub use crate::query::sink::SinkFormat;
+pub use crate::query::parser::{parse_query, ParseError};
+pub use crate::query::schema::Schema;
+pub use crate::query::source::InlineSource;
+pub use crate::query::source::InputFormat;
+pub use crate::query::source::SourceDescriptor;
 pub use crate::query::window::window_descriptor::WindowDescriptor;
 pub use crate::query::window::aggregation::Aggregation;
 pub use crate::query::window::aggregation::AggregationType;
@@ -17,3 +31,8 @@ pub use crate::expression::unary_expression::UnaryExpr;
 pub use crate::expression::unary_expression::UnaryOp;
 pub use crate::expression::binary_expression::BinaryExpr;
 pub use crate::expression::binary_expression::BinaryOp;
+pub use crate::results::result_error::ResultError;
+pub use crate::results::result_reader::ResultReader;
+pub use crate::results::row::compare_rows;
+pub use crate::results::row::FieldValue;
+pub use crate::results::row::Row;
@@ -17,30 +17,64 @@ pub struct Join {
 
 pub struct JoinWhereBuilder {
     query_builder: QueryBuilder,
+    joined_query: QueryBuilder,
 }
 
 pub struct JoinEqualsBuilder {
     query_builder: QueryBuilder,
+    joined_query: QueryBuilder,
+    lhs: Field,
 }
 
 pub struct JoinWindowBuilder {
     query_builder: QueryBuilder,
+    joined_query: QueryBuilder,
+    lhs: Field,
+    rhs: Field,
 }
 
 impl JoinWhereBuilder {
+    pub(super) fn new(query_builder: QueryBuilder, joined_query: QueryBuilder) -> Self {
+        Self {
+            query_builder,
+            joined_query,
+        }
+    }
+
+    /// Sets the key field of the left side of the join.
     pub fn where_field(self, field: impl Into<Field>) -> JoinEqualsBuilder {
-        unimplemented!()
+        JoinEqualsBuilder {
+            query_builder: self.query_builder,
+            joined_query: self.joined_query,
+            lhs: field.into(),
+        }
     }
 }
 
 impl JoinEqualsBuilder {
+    /// Sets the key field of the right side of the join.
     pub fn equals(self, field: impl Into<Field>) -> JoinWindowBuilder {
-        unimplemented!()
+        JoinWindowBuilder {
+            query_builder: self.query_builder,
+            joined_query: self.joined_query,
+            lhs: self.lhs,
+            rhs: field.into(),
+        }
     }
 }
 
 impl JoinWindowBuilder {
-    pub fn window(self, descriptor: WindowDescriptor) -> QueryBuilder {
-        unimplemented!()
+    /// Sets the window in which tuples of both sides are joined and adds the `Join` `Operator` to
+    /// the operator tree.
+    pub fn window(mut self, descriptor: WindowDescriptor) -> QueryBuilder {
+        let child_operator = self.query_builder.operator;
+        self.query_builder.operator = Operator::Join(Join {
+            child: Some(Box::new(child_operator)),
+            joined_operators: Box::new(self.joined_query.operator),
+            lhs: self.lhs,
+            rhs: self.rhs,
+            window: descriptor,
+        });
+        self.query_builder
     }
 }
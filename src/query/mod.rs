@@ -1,9 +1,13 @@
 pub mod sink;
+pub mod source;
 pub mod operator;
 pub mod query;
 pub mod time;
 pub mod window;
 pub mod join;
 pub mod stringify;
+pub mod parser;
+pub mod schema;
+pub mod schema_inference;
 
 pub use query::{Query, QueryBuilder};
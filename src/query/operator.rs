@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     join::Join,
+    source::InlineSource,
+    time::{Duration, WatermarkStrategy},
     window::{aggregation::Aggregation, window_descriptor::WindowDescriptor},
 };
 use crate::expression::{ArithmeticExpr, LogicalExpr};
@@ -27,6 +29,9 @@ pub struct Window {
     pub descriptor: WindowDescriptor,
     pub aggregations: Vec<Aggregation>,
     pub key_fields: Option<Vec<String>>,
+    /// How long the window waits for out-of-order tuples. `None` uses the coordinator's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_lateness: Option<Duration>,
     pub child: Option<Box<Operator>>,
 }
 
@@ -42,27 +47,62 @@ pub struct Projection {
     pub child: Option<Box<Operator>>,
 }
 
+/// Forwards only the first `limit` tuples of the stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Limit {
+    pub limit: u64,
+    pub child: Option<Box<Operator>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatermarkAssigner {
+    pub strategy: WatermarkStrategy,
+    pub child: Option<Box<Operator>>,
+}
+
+/// Qualifies all fields of the stream with `new_source_name`, e.g. `orders$id` becomes
+/// `archive$id`. Needed to distinguish the fields of both sides of a self-join.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rename {
+    pub new_source_name: String,
+    pub child: Option<Box<Operator>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Operator {
-    LogicalSource { source_name: String },
+    /// A source registered with the coordinator. If `physical_source_name` is set, the query only
+    /// reads from this physical source of the logical source.
+    LogicalSource {
+        source_name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        physical_source_name: Option<String>,
+    },
+    InlineSource(InlineSource),
     Projection(Projection),
     Filter(Filter),
     Map(Map),
     Window(Window),
     Join(Join),
     Union(Union),
+    Rename(Rename),
+    Limit(Limit),
+    WatermarkAssigner(WatermarkAssigner),
 }
 
 impl Operator {
     pub fn child(&self) -> Option<&Operator> {
         match self {
             Operator::LogicalSource { .. } => None,
+            Operator::InlineSource(_) => None,
             Operator::Projection(Projection{child, ..}) => child.as_deref(),
             Operator::Filter(Filter { child, .. }) => child.as_deref(),
             Operator::Map(Map { child, .. }) => child.as_deref(),
             Operator::Window(Window { child, .. }) => child.as_deref(),
             Operator::Join(Join { child, .. }) => child.as_deref(),
             Operator::Union(Union { child, .. }) => child.as_deref(),
+            Operator::Rename(Rename { child, .. }) => child.as_deref(),
+            Operator::Limit(Limit { child, .. }) => child.as_deref(),
+            Operator::WatermarkAssigner(WatermarkAssigner { child, .. }) => child.as_deref(),
         }
     }
 
@@ -80,13 +120,26 @@ impl Operator {
 impl Display for Operator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Operator::LogicalSource { source_name } => write!(f, "LogicalSource({source_name})"),
+            Operator::LogicalSource {
+                source_name,
+                physical_source_name: None,
+            } => write!(f, "LogicalSource({source_name})"),
+            Operator::LogicalSource {
+                source_name,
+                physical_source_name: Some(physical_source_name),
+            } => write!(f, "LogicalSource({source_name}, {physical_source_name})"),
+            Operator::InlineSource(source) => {
+                write!(f, "InlineSource({}, {})", source.name, source.descriptor)
+            }
             Operator::Projection(_) => write!(f, "Project(TODO!!!)"),
             Operator::Filter(_) => write!(f, "Filter(TODO!!!)"),
             Operator::Map(_) => write!(f, "Map(TODO!!!)"),
             Operator::Window(_) => write!(f, "Window(TODO!!!)"),
             Operator::Join(_) => write!(f, "Join(TODO!!!)"),
             Operator::Union(_) => write!(f, "Union(TODO!!!)"),
+            Operator::Rename(rename) => write!(f, "Rename({})", rename.new_source_name),
+            Operator::Limit(limit) => write!(f, "Limit({})", limit.limit),
+            Operator::WatermarkAssigner(_) => write!(f, "WatermarkAssigner"),
         }
     }
 }
@@ -108,6 +161,16 @@ impl<'a> Iterator for OperatorIterator<'a> {
 #[cfg(test)]
 mod operator_tests {
     use super::Operator as O;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_limit_serde() {
+        let query = QueryBuilder::from_source("test").limit(10).sink(Sink::Print);
+        let ron = ron::to_string(&query).unwrap();
+        let deser: Query = ron::from_str(&ron).unwrap();
+        assert!(matches!(deser.operator(), O::Limit(limit) if limit.limit == 10));
+    }
+
     // #[test]
     // fn test_operator_iter() {
     //     let operators = O::Filter(Filter {
@@ -0,0 +1,1276 @@
+//! Parses the textual form of queries that is produced by `stringify_query`.
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use nes_types::{FloatType, IntType, NesType};
+
+use super::{
+    sink::{MqttQualityOfService, MqttTimeUnit, OpcIdentifierType, Password, Sink, SinkFormat},
+    time::{Duration, TimeCharacteristic, TimeUnit, WatermarkStrategy},
+    window::{aggregation::Aggregation, window_descriptor::WindowDescriptor},
+    Query, QueryBuilder,
+};
+use crate::expression::{literal::Literal, ArithmeticExpr, ExprBuilder, Field, LogicalExpr};
+
+/// A range of byte offsets into the parsed source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Error returned if a query string can not be parsed. Line and column are 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    message: String,
+    span: Span,
+    line: usize,
+    column: usize,
+}
+
+impl ParseError {
+    fn new(source: &str, span: Span, message: impl Into<String>) -> Self {
+        let before = &source[..span.start];
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+        Self {
+            message: message.into(),
+            span,
+            line,
+            column,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Renders the error together with the offending line of `source` and marks the span.
+    pub fn report(&self, source: &str) -> String {
+        let line = source.lines().nth(self.line - 1).unwrap_or_default();
+        let width = source[self.span.start..self.span.end]
+            .chars()
+            .take_while(|c| *c != '\n')
+            .count()
+            .max(1);
+        let gutter = self.line.to_string();
+        format!(
+            "error: {}\n{gutter} | {line}\n{} | {}{}",
+            self.message,
+            " ".repeat(gutter.len()),
+            " ".repeat(self.column - 1),
+            "^".repeat(width),
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a query in the format produced by `stringify_query`, e.g.
+/// `logical_source("x").filter((Field("a") > 0)).sink(PrintSink);`. Parentheses around binary
+/// expressions are optional, operators bind with the usual precedence.
+pub fn parse_query(source: &str) -> Result<Query, ParseError> {
+    let mut parser = Parser::new(source)?;
+    let query = parser.operator_chain()?;
+    parser.expect_symbol(".")?;
+    parser.expect_keyword("sink")?;
+    parser.expect_symbol("(")?;
+    let sink = parser.sink()?;
+    parser.expect_symbol(")")?;
+    parser.eat_symbol(";");
+    parser.expect_end()?;
+    Ok(query.sink(sink))
+}
+
+impl FromStr for Query {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_query(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    Str(String),
+    Int(String),
+    Float(String),
+    Symbol(&'static str),
+    End,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    span: Span,
+}
+
+const SYMBOLS: [&str; 19] = [
+    "&&", "||", "==", ">=", "<=", ">", "<", "!", "(", ")", ",", ".", ";", "+", "-", "*", "/", "%",
+    "^",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let kind = if c.is_ascii_digit() {
+            let mut end = start;
+            let mut is_float = false;
+            while let Some(&(i, c)) = chars.peek() {
+                let is_fraction = c == '.'
+                    && !is_float
+                    && source[i + 1..].starts_with(|c: char| c.is_ascii_digit());
+                if !c.is_ascii_digit() && !is_fraction {
+                    break;
+                }
+                is_float |= is_fraction;
+                end = i + 1;
+                chars.next();
+            }
+            let text = source[start..end].to_string();
+            if is_float {
+                TokenKind::Float(text)
+            } else {
+                TokenKind::Int(text)
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if !c.is_alphanumeric() && c != '_' {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            TokenKind::Ident(source[start..end].to_string())
+        } else if c == '"' {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, c @ ('"' | '\\'))) => value.push(c),
+                        Some((i, c)) => {
+                            let span = Span {
+                                start: i - 1,
+                                end: i + c.len_utf8(),
+                            };
+                            return Err(ParseError::new(source, span, "unknown escape sequence"));
+                        }
+                        None => continue,
+                    },
+                    Some((_, c)) => value.push(c),
+                    None => {
+                        let span = Span {
+                            start,
+                            end: source.len(),
+                        };
+                        return Err(ParseError::new(source, span, "unterminated string"));
+                    }
+                }
+            }
+            TokenKind::Str(value)
+        } else if let Some(symbol) = SYMBOLS.iter().find(|s| source[start..].starts_with(**s)) {
+            for _ in 0..symbol.len() {
+                chars.next();
+            }
+            TokenKind::Symbol(symbol)
+        } else {
+            let span = Span {
+                start,
+                end: start + c.len_utf8(),
+            };
+            return Err(ParseError::new(
+                source,
+                span,
+                format!("unexpected character `{c}`"),
+            ));
+        };
+        let end = chars.peek().map_or(source.len(), |(i, _)| *i);
+        tokens.push(Token {
+            kind,
+            span: Span { start, end },
+        });
+    }
+    tokens.push(Token {
+        kind: TokenKind::End,
+        span: Span {
+            start: source.len(),
+            end: source.len(),
+        },
+    });
+    Ok(tokens)
+}
+
+fn describe(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Ident(name) => format!("`{name}`"),
+        TokenKind::Str(value) => format!("\"{value}\""),
+        TokenKind::Int(text) | TokenKind::Float(text) => format!("`{text}`"),
+        TokenKind::Symbol(symbol) => format!("`{symbol}`"),
+        TokenKind::End => "end of input".to_string(),
+    }
+}
+
+/// Upper bound for the nesting of expressions and queries. Nested expressions and queries are
+/// parsed recursively, so this bounds the recursion depth.
+const MAX_DEPTH: usize = 100;
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    position: usize,
+    previous_end: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Result<Self, ParseError> {
+        Ok(Self {
+            source,
+            tokens: tokenize(source)?,
+            position: 0,
+            previous_end: 0,
+            depth: 0,
+        })
+    }
+
+    // Token helpers
+
+    fn peek_nth(&self, n: usize) -> &Token {
+        let index = (self.position + n).min(self.tokens.len() - 1);
+        &self.tokens[index]
+    }
+
+    fn peek(&self) -> &Token {
+        self.peek_nth(0)
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.peek().clone();
+        if token.kind != TokenKind::End {
+            self.position += 1;
+        }
+        self.previous_end = token.span.end;
+        token
+    }
+
+    fn error(&self, span: Span, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.source, span, message)
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        let token = self.peek();
+        self.error(
+            token.span,
+            format!("expected {expected}, found {}", describe(&token.kind)),
+        )
+    }
+
+    /// Calls `parse` one level deeper. Fails if this exceeds `MAX_DEPTH`.
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        if self.depth == MAX_DEPTH {
+            let span = self.peek().span;
+            return Err(self.error(span, "too deeply nested"));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn is_symbol(&self, n: usize, symbol: &str) -> bool {
+        matches!(self.peek_nth(n).kind, TokenKind::Symbol(s) if s == symbol)
+    }
+
+    fn is_keyword(&self, n: usize, keyword: &str) -> bool {
+        matches!(&self.peek_nth(n).kind, TokenKind::Ident(name) if name == keyword)
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let found = self.is_symbol(0, symbol);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), ParseError> {
+        if !self.eat_symbol(symbol) {
+            return Err(self.unexpected(&format!("`{symbol}`")));
+        }
+        Ok(())
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        if !self.is_keyword(0, keyword) {
+            return Err(self.unexpected(&format!("`{keyword}`")));
+        }
+        self.advance();
+        Ok(())
+    }
+
+    fn expect_end(&self) -> Result<(), ParseError> {
+        if self.peek().kind != TokenKind::End {
+            return Err(self.unexpected("end of input"));
+        }
+        Ok(())
+    }
+
+    fn ident(&mut self) -> Result<(String, Span), ParseError> {
+        match self.peek().kind.clone() {
+            TokenKind::Ident(name) => Ok((name, self.advance().span)),
+            _ => Err(self.unexpected("an identifier")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        match self.peek().kind.clone() {
+            TokenKind::Str(value) => {
+                self.advance();
+                Ok(value)
+            }
+            _ => Err(self.unexpected("a string")),
+        }
+    }
+
+    /// A name that is either an identifier or a string.
+    fn name(&mut self) -> Result<String, ParseError> {
+        match self.peek().kind.clone() {
+            TokenKind::Ident(value) | TokenKind::Str(value) => {
+                self.advance();
+                Ok(value)
+            }
+            _ => Err(self.unexpected("a name")),
+        }
+    }
+
+    fn unsigned<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let token = self.peek().clone();
+        let TokenKind::Int(text) = &token.kind else {
+            return Err(self.unexpected("an unsigned integer"));
+        };
+        self.advance();
+        text.parse()
+            .map_err(|_| self.error(token.span, format!("integer `{text}` is out of range")))
+    }
+
+    fn boolean(&mut self) -> Result<bool, ParseError> {
+        if self.is_keyword(0, "true") || self.is_keyword(0, "false") {
+            return Ok(self.ident()?.0 == "true");
+        }
+        Err(self.unexpected("`true` or `false`"))
+    }
+
+    /// Parses comma separated items up to and including the closing parenthesis. A trailing
+    /// comma is allowed.
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = Vec::new();
+        while !self.eat_symbol(")") {
+            items.push(item(self)?);
+            if !self.eat_symbol(",") {
+                self.expect_symbol(")")?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    // Operators
+
+    fn operator_chain(&mut self) -> Result<QueryBuilder, ParseError> {
+        if self.is_keyword(0, "inline_source") {
+            let span = self.peek().span;
+            return Err(self.error(
+                span,
+                "inline sources can not be parsed, use the RON format instead",
+            ));
+        }
+        self.expect_keyword("logical_source")?;
+        self.expect_symbol("(")?;
+        let source_name = self.string()?;
+        let mut query = if self.eat_symbol(",") {
+            QueryBuilder::from_physical_source(source_name, self.string()?)
+        } else {
+            QueryBuilder::from_source(source_name)
+        };
+        self.expect_symbol(")")?;
+        while self.is_symbol(0, ".") && !self.is_keyword(1, "sink") {
+            self.advance();
+            query = self.operator(query)?;
+        }
+        Ok(query)
+    }
+
+    fn operator(&mut self, query: QueryBuilder) -> Result<QueryBuilder, ParseError> {
+        let (name, span) = self.ident()?;
+        self.expect_symbol("(")?;
+        let query = match name.as_str() {
+            "filter" => {
+                let predicate = self.logical_expr()?;
+                self.expect_symbol(")")?;
+                query.filter(predicate)
+            }
+            "map" => {
+                let assigned_field = self.name()?;
+                self.expect_symbol(",")?;
+                let expression = self.arith_expr()?;
+                self.expect_symbol(")")?;
+                query.map(assigned_field, expression)
+            }
+            "project" => query.project(self.list(Self::projected_field)?),
+            "window" => {
+                let descriptor = self.window_descriptor()?;
+                self.expect_symbol(")")?;
+                let mut windowed = query.window(descriptor);
+                while self.is_symbol(0, ".") && self.is_keyword(1, "by_key") {
+                    self.advance();
+                    self.advance();
+                    self.expect_symbol("(")?;
+                    windowed = windowed.by_key(self.string()?);
+                    self.expect_symbol(")")?;
+                }
+                if self.is_symbol(0, ".") && self.is_keyword(1, "allowed_lateness") {
+                    let allowed_lateness = self.call("allowed_lateness", Self::duration)?;
+                    windowed = windowed.allowed_lateness(allowed_lateness);
+                }
+                self.expect_symbol(".")?;
+                self.expect_keyword("apply")?;
+                self.expect_symbol("(")?;
+                windowed.apply(self.list(Self::aggregation)?)
+            }
+            "join_with" => {
+                let joined = self.nested(Self::operator_chain)?;
+                self.expect_symbol(")")?;
+                let lhs = self.call("where_field", Self::field)?;
+                let rhs = self.call("equals", Self::field)?;
+                let window = self.call("window", Self::window_descriptor)?;
+                query
+                    .join_with(joined)
+                    .where_field(lhs)
+                    .equals(rhs)
+                    .window(window)
+            }
+            "union" => {
+                let other = self.nested(Self::operator_chain)?;
+                self.expect_symbol(")")?;
+                query.union(other)
+            }
+            "assign_watermark" => {
+                let strategy = self.watermark_strategy()?;
+                self.expect_symbol(")")?;
+                query.assign_watermark(strategy)
+            }
+            "limit" => {
+                let limit = self.unsigned()?;
+                self.expect_symbol(")")?;
+                query.limit(limit)
+            }
+            "as" => {
+                let source_name = self.string()?;
+                self.expect_symbol(")")?;
+                query.rename(source_name)
+            }
+            _ => return Err(self.error(span, format!("unknown operator `{name}`"))),
+        };
+        Ok(query)
+    }
+
+    /// Parses `.name(argument)`.
+    fn call<T>(
+        &mut self,
+        name: &str,
+        argument: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        self.expect_symbol(".")?;
+        self.expect_keyword(name)?;
+        self.expect_symbol("(")?;
+        let result = argument(self)?;
+        self.expect_symbol(")")?;
+        Ok(result)
+    }
+
+    fn field(&mut self) -> Result<Field, ParseError> {
+        self.expect_keyword("Field")?;
+        self.expect_symbol("(")?;
+        let name = self.string()?;
+        self.expect_symbol(")")?;
+        Ok(Field::untyped(name))
+    }
+
+    fn as_suffix(&mut self) -> Result<Option<String>, ParseError> {
+        if !(self.is_symbol(0, ".") && self.is_keyword(1, "as")) {
+            return Ok(None);
+        }
+        self.call("as", Self::string).map(Some)
+    }
+
+    fn projected_field(&mut self) -> Result<Field, ParseError> {
+        let field = self.field()?;
+        Ok(match self.as_suffix()? {
+            Some(name) => field.rename(name),
+            None => field,
+        })
+    }
+
+    fn aggregation(&mut self) -> Result<Aggregation, ParseError> {
+        let (name, span) = self.ident()?;
+        self.expect_symbol("(")?;
+        let (field, projected) = if self.is_symbol(0, ")") {
+            (None, None)
+        } else if self.is_keyword(0, "as") {
+            self.advance();
+            self.expect_symbol("(")?;
+            let projected = self.string()?;
+            self.expect_symbol(")")?;
+            (None, Some(projected))
+        } else {
+            (Some(self.field()?), self.as_suffix()?)
+        };
+        self.expect_symbol(")")?;
+        let aggregation = match (name.as_str(), field) {
+            ("count", None) => Aggregation::count(),
+            ("sum", Some(field)) => Aggregation::sum(field),
+            ("average", Some(field)) => Aggregation::average(field),
+            ("min", Some(field)) => Aggregation::min(field),
+            ("max", Some(field)) => Aggregation::max(field),
+            ("median", Some(field)) => Aggregation::median(field),
+            ("count", Some(_)) => return Err(self.error(span, "`count` does not take a field")),
+            ("sum" | "average" | "min" | "max" | "median", None) => {
+                return Err(self.error(span, format!("`{name}` requires a field")))
+            }
+            _ => return Err(self.error(span, format!("unknown aggregation `{name}`"))),
+        };
+        Ok(match projected {
+            Some(projected) => aggregation.as_field(projected),
+            None => aggregation,
+        })
+    }
+
+    fn window_descriptor(&mut self) -> Result<WindowDescriptor, ParseError> {
+        let (name, span) = self.ident()?;
+        self.expect_symbol("(")?;
+        let descriptor = match name.as_str() {
+            "TumblingWindow" => {
+                let duration = self.duration()?;
+                self.expect_symbol(",")?;
+                WindowDescriptor::tumbling(duration, self.time_characteristic()?)
+            }
+            "SlidingWindow" => {
+                let size = self.duration()?;
+                self.expect_symbol(",")?;
+                let slide = self.duration()?;
+                self.expect_symbol(",")?;
+                WindowDescriptor::sliding(size, slide, self.time_characteristic()?)
+            }
+            "ThresholdWindow" => {
+                let predicate = self.logical_expr()?;
+                self.expect_symbol(",")?;
+                WindowDescriptor::threshold(predicate, self.unsigned()?)
+            }
+            _ => return Err(self.error(span, format!("unknown window type `{name}`"))),
+        };
+        self.expect_symbol(")")?;
+        Ok(descriptor)
+    }
+
+    fn duration(&mut self) -> Result<Duration, ParseError> {
+        let amount = self.unsigned()?;
+        let (unit, span) = self.ident()?;
+        let unit = match unit.as_str() {
+            "ms" => TimeUnit::Milliseconds,
+            "s" => TimeUnit::Seconds,
+            "min" => TimeUnit::Minutes,
+            "h" => TimeUnit::Hours,
+            "d" => TimeUnit::Days,
+            _ => return Err(self.error(span, format!("unknown time unit `{unit}`"))),
+        };
+        Ok(Duration { amount, unit })
+    }
+
+    fn time_characteristic(&mut self) -> Result<TimeCharacteristic, ParseError> {
+        let (name, span) = self.ident()?;
+        self.expect_symbol("(")?;
+        let time_character = match name.as_str() {
+            "EventTime" => {
+                let field_name = self.string()?;
+                self.expect_symbol(",")?;
+                TimeCharacteristic::event_time(field_name, self.time_unit()?)
+            }
+            "IngestionTime" => TimeCharacteristic::ingestion_time(),
+            _ => return Err(self.error(span, format!("unknown time characteristic `{name}`"))),
+        };
+        self.expect_symbol(")")?;
+        Ok(time_character)
+    }
+
+    fn watermark_strategy(&mut self) -> Result<WatermarkStrategy, ParseError> {
+        let (name, span) = self.ident()?;
+        self.expect_symbol("(")?;
+        let strategy = match name.as_str() {
+            "EventTimeWatermark" => {
+                let field_name = self.string()?;
+                self.expect_symbol(",")?;
+                let allowed_lateness = self.duration()?;
+                self.expect_symbol(",")?;
+                WatermarkStrategy::event_time(field_name, allowed_lateness, self.time_unit()?)
+            }
+            "IngestionTimeWatermark" => WatermarkStrategy::ingestion_time(),
+            _ => return Err(self.error(span, format!("unknown watermark strategy `{name}`"))),
+        };
+        self.expect_symbol(")")?;
+        Ok(strategy)
+    }
+
+    fn time_unit(&mut self) -> Result<TimeUnit, ParseError> {
+        let (unit, span) = self.ident()?;
+        match unit.as_str() {
+            "Milliseconds" => Ok(TimeUnit::Milliseconds),
+            "Seconds" => Ok(TimeUnit::Seconds),
+            "Minutes" => Ok(TimeUnit::Minutes),
+            "Hours" => Ok(TimeUnit::Hours),
+            "Days" => Ok(TimeUnit::Days),
+            _ => Err(self.error(span, format!("unknown time unit `{unit}`"))),
+        }
+    }
+
+    fn sink(&mut self) -> Result<Sink, ParseError> {
+        let (name, span) = self.ident()?;
+        match name.as_str() {
+            "NullOutputSink" => Ok(Sink::NullOutput),
+            "PrintSink" => Ok(Sink::Print),
+            "FileSink" => {
+                self.expect_symbol("(")?;
+                let path = self.string()?;
+                self.expect_symbol(",")?;
+                let span = self.peek().span;
+                let name = self.name()?;
+                let Some(format) = SinkFormat::from_coordinator_str(&name) else {
+                    return Err(self.error(span, format!("unknown sink format `{name}`")));
+                };
+                self.expect_symbol(",")?;
+                let append = self.boolean()?;
+                let add_timestamp = self.eat_symbol(",") && self.boolean()?;
+                self.expect_symbol(")")?;
+                Ok(Sink::File {
+                    path,
+                    format,
+                    append,
+                    add_timestamp,
+                })
+            }
+            "KafkaSink" => {
+                self.expect_symbol("(")?;
+                let brokers = self.string()?;
+                self.expect_symbol(",")?;
+                let topic = self.string()?;
+                self.expect_symbol(",")?;
+                let connect_timeout = self.unsigned()?;
+                self.expect_symbol(")")?;
+                Ok(Sink::kafka(brokers, topic, connect_timeout))
+            }
+            "MQTTSink" => {
+                self.expect_symbol("(")?;
+                let address = self.string()?;
+                self.expect_symbol(",")?;
+                let client_id = self.string()?;
+                self.expect_symbol(",")?;
+                let topic = self.string()?;
+                self.expect_symbol(",")?;
+                let user = self.string()?;
+                self.expect_symbol(",")?;
+                let max_buffered_messages = self.unsigned()?;
+                self.expect_symbol(",")?;
+                let time_unit = self.mqtt_time_unit()?;
+                self.expect_symbol(",")?;
+                let message_delay = self.unsigned()?;
+                self.expect_symbol(",")?;
+                let quality_of_service = self.mqtt_quality_of_service()?;
+                self.expect_symbol(",")?;
+                let asynchronous_client = self.boolean()?;
+                self.expect_symbol(")")?;
+                Ok(Sink::Mqtt {
+                    address,
+                    client_id,
+                    topic,
+                    user,
+                    max_buffered_messages,
+                    time_unit,
+                    message_delay,
+                    quality_of_service,
+                    asynchronous_client,
+                })
+            }
+            "ZMQSink" => {
+                self.expect_symbol("(")?;
+                let host = self.string()?;
+                self.expect_symbol(",")?;
+                let port = self.unsigned()?;
+                self.expect_symbol(")")?;
+                Ok(Sink::zmq(host, port))
+            }
+            "OPCSink" => {
+                self.expect_symbol("(")?;
+                let url = self.string()?;
+                self.expect_symbol(",")?;
+                let namespace_index = self.unsigned()?;
+                self.expect_symbol(",")?;
+                let identifier = self.string()?;
+                self.expect_symbol(",")?;
+                let identifier_type = self.opc_identifier_type()?;
+                // user and password are optional since they are not stringified
+                let (mut user, mut password) = (String::new(), Password::default());
+                if self.eat_symbol(",") {
+                    user = self.string()?;
+                    self.expect_symbol(",")?;
+                    password = Password::new(self.string()?);
+                }
+                self.expect_symbol(")")?;
+                Ok(Sink::Opc {
+                    url,
+                    namespace_index,
+                    identifier,
+                    identifier_type,
+                    user,
+                    password,
+                })
+            }
+            _ => Err(self.error(span, format!("unknown sink `{name}`"))),
+        }
+    }
+
+    fn mqtt_time_unit(&mut self) -> Result<MqttTimeUnit, ParseError> {
+        let (name, span) = self.ident()?;
+        match name.as_str() {
+            "Nanoseconds" => Ok(MqttTimeUnit::Nanoseconds),
+            "Milliseconds" => Ok(MqttTimeUnit::Milliseconds),
+            "Seconds" => Ok(MqttTimeUnit::Seconds),
+            _ => Err(self.error(span, format!("unknown MQTT time unit `{name}`"))),
+        }
+    }
+
+    fn mqtt_quality_of_service(&mut self) -> Result<MqttQualityOfService, ParseError> {
+        let (name, span) = self.ident()?;
+        match name.as_str() {
+            "AtMostOnce" => Ok(MqttQualityOfService::AtMostOnce),
+            "AtLeastOnce" => Ok(MqttQualityOfService::AtLeastOnce),
+            "ExactlyOnce" => Ok(MqttQualityOfService::ExactlyOnce),
+            _ => Err(self.error(span, format!("unknown MQTT quality of service `{name}`"))),
+        }
+    }
+
+    fn opc_identifier_type(&mut self) -> Result<OpcIdentifierType, ParseError> {
+        let (name, span) = self.ident()?;
+        match name.as_str() {
+            "Numeric" => Ok(OpcIdentifierType::Numeric),
+            "String" => Ok(OpcIdentifierType::String),
+            "Guid" => Ok(OpcIdentifierType::Guid),
+            "ByteString" => Ok(OpcIdentifierType::ByteString),
+            _ => Err(self.error(span, format!("unknown OPC identifier type `{name}`"))),
+        }
+    }
+
+    // Expressions
+
+    fn logical_expr(&mut self) -> Result<LogicalExpr, ParseError> {
+        let start = self.peek().span.start;
+        let expr = self.expr()?;
+        let span = Span {
+            start,
+            end: self.previous_end,
+        };
+        expr.build_logical()
+            .map_err(|_| self.error(span, "expected a well-typed boolean expression"))
+    }
+
+    fn arith_expr(&mut self) -> Result<ArithmeticExpr, ParseError> {
+        let start = self.peek().span.start;
+        let expr = self.expr()?;
+        let span = Span {
+            start,
+            end: self.previous_end,
+        };
+        expr.build_arith()
+            .map_err(|_| self.error(span, "expected a well-typed arithmetic expression"))
+    }
+
+    fn expr(&mut self) -> Result<ExprBuilder, ParseError> {
+        self.nested(|parser| {
+            let mut lhs = parser.and_expr()?;
+            while parser.eat_symbol("||") {
+                lhs = lhs.or(parser.and_expr()?);
+            }
+            Ok(lhs)
+        })
+    }
+
+    fn and_expr(&mut self) -> Result<ExprBuilder, ParseError> {
+        let mut lhs = self.cmp_expr()?;
+        while self.eat_symbol("&&") {
+            lhs = lhs.and(self.cmp_expr()?);
+        }
+        Ok(lhs)
+    }
+
+    fn cmp_expr(&mut self) -> Result<ExprBuilder, ParseError> {
+        let lhs = self.additive_expr()?;
+        let operator: fn(ExprBuilder, ExprBuilder) -> ExprBuilder = match self.peek().kind {
+            TokenKind::Symbol("==") => ExprBuilder::equals,
+            TokenKind::Symbol(">") => ExprBuilder::greater_than,
+            TokenKind::Symbol(">=") => ExprBuilder::greater_equals,
+            TokenKind::Symbol("<") => ExprBuilder::less_than,
+            TokenKind::Symbol("<=") => ExprBuilder::less_equals,
+            _ => return Ok(lhs),
+        };
+        self.advance();
+        Ok(operator(lhs, self.additive_expr()?))
+    }
+
+    fn additive_expr(&mut self) -> Result<ExprBuilder, ParseError> {
+        let mut lhs = self.multiplicative_expr()?;
+        loop {
+            lhs = match self.peek().kind {
+                TokenKind::Symbol("+") => {
+                    self.advance();
+                    lhs + self.multiplicative_expr()?
+                }
+                TokenKind::Symbol("-") => {
+                    self.advance();
+                    lhs - self.multiplicative_expr()?
+                }
+                _ => return Ok(lhs),
+            };
+        }
+    }
+
+    fn multiplicative_expr(&mut self) -> Result<ExprBuilder, ParseError> {
+        let mut lhs = self.power_expr()?;
+        loop {
+            let operator: fn(ExprBuilder, ExprBuilder) -> ExprBuilder = match self.peek().kind {
+                TokenKind::Symbol("*") => |lhs, rhs| lhs * rhs,
+                TokenKind::Symbol("/") => |lhs, rhs| lhs / rhs,
+                TokenKind::Symbol("%") => |lhs, rhs| lhs % rhs,
+                _ => return Ok(lhs),
+            };
+            self.advance();
+            lhs = operator(lhs, self.power_expr()?);
+        }
+    }
+
+    fn power_expr(&mut self) -> Result<ExprBuilder, ParseError> {
+        let base = self.unary_expr()?;
+        if self.eat_symbol("^") {
+            return Ok(base.pow(self.nested(Self::power_expr)?));
+        }
+        Ok(base)
+    }
+
+    fn unary_expr(&mut self) -> Result<ExprBuilder, ParseError> {
+        if self.eat_symbol("!") {
+            return Ok(self.nested(Self::unary_expr)?.not());
+        }
+        if self.eat_symbol("-") {
+            let token = self.peek().clone();
+            return match &token.kind {
+                TokenKind::Int(text) | TokenKind::Float(text) => {
+                    self.number(&format!("-{text}"), token.span)
+                }
+                _ => Err(self.unexpected("a number")),
+            };
+        }
+        self.primary_expr()
+    }
+
+    /// Parses a number. A type suffix like `i64` or `f32` directly following the number sets its
+    /// type. Otherwise numbers with a fraction are `FLOAT(64 bits)` and integers are
+    /// `INTEGER(32 bits)` if they fit, else `INTEGER(64 bits)`.
+    fn number(&mut self, text: &str, span: Span) -> Result<ExprBuilder, ParseError> {
+        self.advance();
+        let suffix = match &self.peek().kind {
+            TokenKind::Ident(suffix) if self.peek().span.start == self.previous_end => {
+                Some(suffix.clone())
+            }
+            _ => None,
+        };
+        if let Some(suffix) = suffix {
+            let span = Span {
+                start: span.start,
+                end: self.advance().span.end,
+            };
+            let (data_type, is_valid) = match suffix.as_str() {
+                "i8" => (NesType::Int(IntType::Signed8), text.parse::<i8>().is_ok()),
+                "i16" => (NesType::Int(IntType::Signed16), text.parse::<i16>().is_ok()),
+                "i32" => (NesType::Int(IntType::Signed32), text.parse::<i32>().is_ok()),
+                "i64" => (NesType::Int(IntType::Signed64), text.parse::<i64>().is_ok()),
+                "u8" => (NesType::Int(IntType::Unsigned8), text.parse::<u8>().is_ok()),
+                "u16" => (
+                    NesType::Int(IntType::Unsigned16),
+                    text.parse::<u16>().is_ok(),
+                ),
+                "u32" => (
+                    NesType::Int(IntType::Unsigned32),
+                    text.parse::<u32>().is_ok(),
+                ),
+                "u64" => (
+                    NesType::Int(IntType::Unsigned64),
+                    text.parse::<u64>().is_ok(),
+                ),
+                "f32" => (
+                    NesType::Float(FloatType::Bit32),
+                    text.parse::<f32>().is_ok(),
+                ),
+                "f64" => (
+                    NesType::Float(FloatType::Bit64),
+                    text.parse::<f64>().is_ok(),
+                ),
+                _ => return Err(self.error(span, format!("unknown number suffix `{suffix}`"))),
+            };
+            if !is_valid {
+                return Err(self.error(span, format!("`{text}` is out of range for `{suffix}`")));
+            }
+            return Ok(ExprBuilder::literal(Literal::typed(text, data_type)));
+        }
+        if text.contains('.') {
+            return Ok(ExprBuilder::literal(Literal::typed(
+                text,
+                NesType::Float(FloatType::Bit64),
+            )));
+        }
+        if let Ok(value) = text.parse::<i32>() {
+            return Ok(ExprBuilder::literal(value));
+        }
+        if let Ok(value) = text.parse::<i64>() {
+            return Ok(ExprBuilder::literal(value));
+        }
+        Err(self.error(span, format!("integer `{text}` is out of range")))
+    }
+
+    fn primary_expr(&mut self) -> Result<ExprBuilder, ParseError> {
+        let token = self.peek().clone();
+        match &token.kind {
+            TokenKind::Int(text) | TokenKind::Float(text) => self.number(text, token.span),
+            TokenKind::Symbol("(") => {
+                self.advance();
+                let expr = self.expr()?;
+                self.expect_symbol(")")?;
+                Ok(expr)
+            }
+            TokenKind::Ident(name) => match name.as_str() {
+                "true" | "false" => Ok(ExprBuilder::literal(self.boolean()?)),
+                "Field" => Ok(ExprBuilder::field(self.field()?.name())),
+                "case" => self.case_expr(),
+                "abs" | "ceil" | "floor" | "round" | "sqrt" | "exp" => {
+                    self.advance();
+                    self.expect_symbol("(")?;
+                    let expr = self.expr()?;
+                    self.expect_symbol(")")?;
+                    Ok(match name.as_str() {
+                        "abs" => expr.abs(),
+                        "ceil" => expr.ceil(),
+                        "floor" => expr.floor(),
+                        "round" => expr.round(),
+                        "sqrt" => expr.sqrt(),
+                        _ => expr.exp(),
+                    })
+                }
+                _ => Err(self.error(token.span, format!("unknown function `{name}`"))),
+            },
+            _ => Err(self.unexpected("an expression")),
+        }
+    }
+
+    /// Parses `case(when(condition, value), ..., default)`.
+    fn case_expr(&mut self) -> Result<ExprBuilder, ParseError> {
+        let span = self.advance().span;
+        self.expect_symbol("(")?;
+        let mut branches = Vec::new();
+        while self.is_keyword(0, "when") && self.is_symbol(1, "(") {
+            self.advance();
+            self.advance();
+            let condition = self.expr()?;
+            self.expect_symbol(",")?;
+            let value = self.expr()?;
+            self.expect_symbol(")")?;
+            self.expect_symbol(",")?;
+            branches.push((condition, value));
+        }
+        let default = self.expr()?;
+        self.expect_symbol(")")?;
+        let mut branches = branches.into_iter();
+        let Some((condition, value)) = branches.next() else {
+            return Err(self.error(span, "`case` requires at least one `when` branch"));
+        };
+        let case = branches.fold(ExprBuilder::when(condition, value), |case, (c, v)| {
+            case.when(c, v)
+        });
+        Ok(case.otherwise(default))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nes_types::{FloatType, IntType, NesType};
+
+    use super::{parse_query, Span};
+    use crate::{
+        prelude::{ExprBuilder as EB, *},
+        query::{
+            operator::Operator,
+            stringify::stringify_query,
+            time::{Duration, TimeCharacteristic, TimeUnit, WatermarkStrategy},
+        },
+    };
+
+    fn assert_round_trip(query: Query) {
+        let string = stringify_query(&query);
+        let parsed = parse_query(&string).unwrap();
+        assert_eq!(string, stringify_query(&parsed));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let time_character = TimeCharacteristic::event_time("ts", TimeUnit::Milliseconds);
+        assert_round_trip(
+            QueryBuilder::from_source("test")
+                .filter(
+                    EB::field("value")
+                        .greater_than(EB::literal(-1))
+                        .and(EB::field("id").equals(EB::literal(1)).not())
+                        .build_logical()
+                        .unwrap(),
+                )
+                .map(
+                    "level",
+                    EB::when(
+                        EB::field("value").greater_than(EB::literal(100)),
+                        EB::literal(2),
+                    )
+                    .otherwise(EB::literal(1))
+                    .build_arith()
+                    .unwrap(),
+                )
+                .map(
+                    "root",
+                    EB::field("value").sqrt().round().build_arith().unwrap(),
+                )
+                .project([Field::untyped("id"), Field::untyped("level").rename("l")])
+                .sink(Sink::csv_file("result.csv", true)),
+        );
+        assert_round_trip(
+            QueryBuilder::from_source("orders")
+                .union(QueryBuilder::from_source("archived_orders"))
+                .join_with(QueryBuilder::from_source("products"))
+                .where_field("product_id")
+                .equals("id")
+                .window(WindowDescriptor::tumbling(
+                    Duration::from_seconds(10),
+                    TimeCharacteristic::ingestion_time(),
+                ))
+                .window(WindowDescriptor::sliding(
+                    Duration::from_minutes(10),
+                    Duration::from_minutes(1),
+                    time_character,
+                ))
+                .by_key("product_id")
+                .allowed_lateness(Duration::from_seconds(30))
+                .apply([
+                    Aggregation::sum("price").as_field("total"),
+                    Aggregation::count().as_field("orders"),
+                ])
+                .sink(Sink::Print),
+        );
+        assert_round_trip(QueryBuilder::from_physical_source("test", "test_1").sink(Sink::Print));
+        assert_round_trip(
+            QueryBuilder::from_source("say \"hi\"")
+                .filter(
+                    EB::field("a\"b")
+                        .greater_than(EB::field("c\\d"))
+                        .build_logical()
+                        .unwrap(),
+                )
+                .project([Field::untyped("a\"b").rename("quoted \"name\"")])
+                .sink(Sink::csv_file("C:\\tmp\\out.csv", false)),
+        );
+        assert_round_trip(
+            QueryBuilder::from_source("test")
+                .rename("current")
+                .union(QueryBuilder::from_source("test").rename("archived"))
+                .limit(100)
+                .map(
+                    "current$doubled",
+                    (EB::field("current$value") * EB::literal(2))
+                        .build_arith()
+                        .unwrap(),
+                )
+                .sink(Sink::Print),
+        );
+        assert_round_trip(
+            QueryBuilder::from_source("test")
+                .assign_watermark(WatermarkStrategy::event_time(
+                    "ts",
+                    Duration::from_seconds(5),
+                    TimeUnit::Milliseconds,
+                ))
+                .union(
+                    QueryBuilder::from_source("other")
+                        .assign_watermark(WatermarkStrategy::ingestion_time()),
+                )
+                .sink(Sink::Print),
+        );
+    }
+
+    #[test]
+    fn test_typed_literals() {
+        let literals: [Literal; 8] = [
+            1.into(),
+            (-7i64).into(),
+            i64::MAX.into(),
+            Literal::typed("1", NesType::Float(FloatType::Bit64)),
+            Literal::typed("2.5", NesType::Float(FloatType::Bit64)),
+            Literal::typed("-2.5", NesType::Float(FloatType::Bit32)),
+            Literal::typed("200", NesType::Int(IntType::Unsigned8)),
+            Literal::typed("-3", NesType::Int(IntType::Signed16)),
+        ];
+        for literal in literals {
+            let expected = EB::literal(literal).build_arith().unwrap();
+            let query = QueryBuilder::from_source("test")
+                .map("value", expected.clone())
+                .sink(Sink::Print);
+            let parsed = parse_query(&stringify_query(&query)).unwrap();
+            let Operator::Map(map) = parsed.operator() else {
+                panic!("expected map operator");
+            };
+            assert_eq!(expected, map.expression);
+        }
+        let err =
+            parse_query(r#"logical_source("test").map("a", Field("a") + 300u8).sink(PrintSink)"#)
+                .unwrap_err();
+        assert_eq!("`300` is out of range for `u8`", err.message());
+        let err =
+            parse_query(r#"logical_source("test").map("a", 1x).sink(PrintSink)"#).unwrap_err();
+        assert_eq!("unknown number suffix `x`", err.message());
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |depth: usize| {
+            format!(
+                r#"logical_source("test").filter({}Field("a") > 1{}).sink(PrintSink)"#,
+                "(".repeat(depth),
+                ")".repeat(depth)
+            )
+        };
+        assert!(parse_query(&nested(50)).is_ok());
+        let err = parse_query(&nested(100_000)).unwrap_err();
+        assert_eq!("too deeply nested", err.message());
+        let source = format!(
+            r#"logical_source("test").filter({}true).sink(PrintSink)"#,
+            "!".repeat(100_000)
+        );
+        assert!(parse_query(&source).is_err());
+        let source = format!(
+            r#"logical_source("test").map("a", 2{}).sink(PrintSink)"#,
+            "^2".repeat(100_000)
+        );
+        assert!(parse_query(&source).is_err());
+    }
+
+    #[test]
+    fn test_sink_round_trip() {
+        let sinks = [
+            Sink::json_file("result.json", false).with_timestamp(),
+            Sink::csv_file("C:\\tmp\\\"out\".csv", true),
+            Sink::binary_file("result.bin", true),
+            Sink::kafka("localhost:9092", "results", 10000),
+            Sink::mqtt(
+                "tcp://localhost:1883",
+                "nes",
+                "results",
+                MqttQualityOfService::AtLeastOnce,
+            ),
+            Sink::zmq("localhost", 5555),
+            Sink::opc(
+                "opc.tcp://localhost:4840",
+                1,
+                "results",
+                OpcIdentifierType::String,
+            ),
+        ];
+        for sink in sinks {
+            let query = QueryBuilder::from_source("test").sink(sink.clone());
+            assert_eq!(&sink, parse_query(&stringify_query(&query)).unwrap().sink());
+        }
+    }
+
+    #[test]
+    fn test_precedence() {
+        let query = parse_query(
+            r#"logical_source("test").filter(Field("a") + 2 * 3 > 4 || !(Field("b") == 1)).sink(NullOutputSink)"#,
+        )
+        .unwrap();
+        let expected = QueryBuilder::from_source("test")
+            .filter(
+                (EB::field("a") + EB::literal(2) * EB::literal(3))
+                    .greater_than(EB::literal(4))
+                    .or(EB::field("b").equals(EB::literal(1)).not())
+                    .build_logical()
+                    .unwrap(),
+            )
+            .sink(Sink::NullOutput);
+        assert_eq!(stringify_query(&expected), stringify_query(&query));
+    }
+
+    #[test]
+    fn test_errors() {
+        let source =
+            "logical_source(\"test\")\n    .filter(Field(\"a\") > 1,)\n    .sink(PrintSink);";
+        let err = parse_query(source).unwrap_err();
+        assert_eq!("expected `)`, found `,`", err.message());
+        assert_eq!((2, 27), (err.line(), err.column()));
+        assert!(err
+            .report(source)
+            .ends_with(&format!("{}^", " ".repeat(26))));
+
+        let source = r#"logical_source("test").filter(Field("a") + 1).sink(PrintSink);"#;
+        let err = parse_query(source).unwrap_err();
+        assert_eq!(Span { start: 30, end: 44 }, err.span());
+
+        let err = parse_query(r#"logical_source("test").sink(FileSink("out.csv"#).unwrap_err();
+        assert_eq!("unterminated string", err.message());
+    }
+}
@@ -2,8 +2,14 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     join::JoinWhereBuilder,
-    operator::{Filter, Map, Operator, OperatorIterator, Projection, Union, Window},
+    operator::{
+        Filter, Limit, Map, Operator, OperatorIterator, Projection, Rename, Union,
+        WatermarkAssigner, Window,
+    },
+    schema::Schema,
     sink::Sink,
+    source::{InlineSource, SourceDescriptor},
+    time::{Duration, WatermarkStrategy},
     window::{aggregation::Aggregation, window_descriptor::WindowDescriptor},
 };
 use crate::expression::{ArithmeticExpr, Field, LogicalExpr};
@@ -23,6 +29,7 @@ pub struct WindowedQueryBuilder {
     query_builder: QueryBuilder,
     key_fields: Option<Vec<String>>,
     descriptor: WindowDescriptor,
+    allowed_lateness: Option<Duration>,
 }
 
 impl WindowedQueryBuilder {
@@ -35,6 +42,12 @@ impl WindowedQueryBuilder {
         self
     }
 
+    /// Keeps the window open for tuples that arrive up to `allowed_lateness` out of order.
+    pub fn allowed_lateness(mut self, allowed_lateness: Duration) -> Self {
+        self.allowed_lateness = Some(allowed_lateness);
+        self
+    }
+
     pub fn apply(mut self, aggregation: impl IntoIterator<Item = Aggregation>) -> QueryBuilder {
         let child_operator = self.query_builder.operator;
         let aggregations = aggregation.into_iter().collect();
@@ -43,6 +56,7 @@ impl WindowedQueryBuilder {
             descriptor: self.descriptor,
             aggregations,
             key_fields: self.key_fields,
+            allowed_lateness: self.allowed_lateness,
         });
         self.query_builder
     }
@@ -50,10 +64,14 @@ impl WindowedQueryBuilder {
 
 #[derive(Debug, Clone)]
 pub struct QueryBuilder {
-    operator: Operator,
+    pub(super) operator: Operator,
 }
 
 impl Query {
+    pub(crate) fn new(operator: Operator, sink: Sink) -> Self {
+        Self { operator, sink }
+    }
+
     pub fn operator(&self) -> &Operator {
         &self.operator
     }
@@ -79,10 +97,46 @@ impl QueryBuilder {
     pub fn from_source(source_name: impl Into<String>) -> Self {
         let operator = Operator::LogicalSource {
             source_name: source_name.into(),
+            physical_source_name: None,
+        };
+        QueryBuilder { operator }
+    }
+
+    /// Reads only from the physical source `physical_source_name` of the logical source.
+    pub fn from_physical_source(
+        source_name: impl Into<String>,
+        physical_source_name: impl Into<String>,
+    ) -> Self {
+        let operator = Operator::LogicalSource {
+            source_name: source_name.into(),
+            physical_source_name: Some(physical_source_name.into()),
         };
         QueryBuilder { operator }
     }
 
+    /// Reads from a source that is not registered with the coordinator.
+    pub fn from_inline_source(
+        name: impl Into<String>,
+        schema: Schema,
+        descriptor: SourceDescriptor,
+    ) -> Self {
+        let operator = Operator::InlineSource(InlineSource {
+            name: name.into(),
+            schema,
+            descriptor,
+        });
+        QueryBuilder { operator }
+    }
+
+    /// Reads a CSV file with a header line, see `SourceDescriptor::csv`.
+    pub fn from_csv_file<P: AsRef<std::path::Path>>(
+        name: impl Into<String>,
+        path: P,
+        schema: Schema,
+    ) -> Self {
+        Self::from_inline_source(name, schema, SourceDescriptor::csv(path))
+    }
+
     pub fn sink(self, sink: Sink) -> Query {
         Query {
             sink,
@@ -117,6 +171,7 @@ impl QueryBuilder {
             query_builder: self,
             descriptor,
             key_fields: None,
+            allowed_lateness: None,
         }
     }
 
@@ -129,8 +184,10 @@ impl QueryBuilder {
         self
     }
 
+    /// Joins this query with `query`. The join keys and the join window are specified through the
+    /// returned `JoinWhereBuilder`.
     pub fn join_with(self, query: Self) -> JoinWhereBuilder {
-        unimplemented!();
+        JoinWhereBuilder::new(self, query)
     }
 
     pub fn union(mut self, query: Self) -> Self {
@@ -142,7 +199,35 @@ impl QueryBuilder {
         self
     }
 
-    pub fn rename(self, source_name: impl Into<String>) -> Self {
-        unimplemented!();
+    /// Assigns watermarks to the stream, e.g. to keep windows open for tuples that are up to five
+    /// seconds late: `WatermarkStrategy::event_time("ts", Duration::from_seconds(5), unit)`.
+    pub fn assign_watermark(mut self, strategy: WatermarkStrategy) -> Self {
+        let child_operator = self.operator;
+        self.operator = Operator::WatermarkAssigner(WatermarkAssigner {
+            strategy,
+            child: Some(Box::new(child_operator)),
+        });
+        self
+    }
+
+    /// Forwards only the first `limit` tuples, e.g. to sample a stream while debugging.
+    pub fn limit(mut self, limit: u64) -> Self {
+        let child_operator = self.operator;
+        self.operator = Operator::Limit(Limit {
+            limit,
+            child: Some(Box::new(child_operator)),
+        });
+        self
+    }
+
+    /// Qualifies all fields with `source_name`, e.g. to join a source with itself:
+    /// `from_source("orders").rename("lhs").join_with(from_source("orders").rename("rhs"))`.
+    pub fn rename(mut self, source_name: impl Into<String>) -> Self {
+        let child_operator = self.operator;
+        self.operator = Operator::Rename(Rename {
+            new_source_name: source_name.into(),
+            child: Some(Box::new(child_operator)),
+        });
+        self
     }
 }
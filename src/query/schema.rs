@@ -0,0 +1,50 @@
+use nes_types::NesType;
+use serde::{Deserialize, Serialize};
+
+use crate::expression::Field;
+
+/// A `Schema` describes the fields of a stream. Every field of a schema is typed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+    fields: Vec<Field>,
+}
+
+impl Schema {
+    pub fn new() -> Self {
+        Self { fields: Vec::new() }
+    }
+
+    /// Appends a field with the given name and type to the schema.
+    pub fn with_field(mut self, name: impl Into<String>, data_type: NesType) -> Self {
+        self.fields.push(Field::typed(name, data_type));
+        self
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name() == name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.field(name).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+impl FromIterator<Field> for Schema {
+    fn from_iter<T: IntoIterator<Item = Field>>(iter: T) -> Self {
+        Self {
+            fields: iter.into_iter().collect(),
+        }
+    }
+}
@@ -0,0 +1,655 @@
+//! Infers the schemas of operators and the types of fields and expressions in a query.
+
+use std::{collections::HashMap, error::Error, fmt::Display};
+
+use nes_types::{FloatType, IntType, NesType};
+
+use super::{
+    join::Join,
+    operator::{
+        Filter, Limit, Map, Operator, Projection, Rename, Union, WatermarkAssigner, Window,
+    },
+    schema::Schema,
+    time::{TimeCharacteristic, WatermarkStrategy},
+    window::{aggregation::AggregationType, window_descriptor::WindowDescriptor},
+    Query,
+};
+use crate::expression::{
+    binary_expression::{BinaryExpr, BinaryOpType},
+    case_expression::{CaseExpr, WhenBranch},
+    expression::RawExpr,
+    unary_expression::UnaryExpr,
+    ArithmeticExpr, Field, LogicalExpr,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaInferenceError {
+    /// The query reads from a logical source for which no schema is known.
+    UnknownSource(String),
+    /// An expression or operator accesses a field that is not part of its input schema.
+    UnknownField(String),
+    /// A field without `source$` qualifier matches fields of several sources.
+    AmbiguousField(String),
+    /// The two inputs of a union have different schemas.
+    UnionSchemaMismatch { lhs: Schema, rhs: Schema },
+    /// The predicate of a filter is not boolean.
+    NonBooleanFilter(NesType),
+    /// The operands of an expression have incompatible types.
+    TypeMismatch(String),
+}
+
+impl Display for SchemaInferenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaInferenceError::UnknownSource(name) => {
+                write!(f, "No schema is known for logical source {name}.")
+            }
+            SchemaInferenceError::UnknownField(name) => {
+                write!(f, "Field {name} is not part of the input schema.")
+            }
+            SchemaInferenceError::AmbiguousField(name) => {
+                write!(
+                    f,
+                    "Field {name} matches multiple fields of the input schema."
+                )
+            }
+            SchemaInferenceError::UnionSchemaMismatch { lhs, rhs } => write!(
+                f,
+                "Union of streams with different schemas {:?} and {:?}.",
+                lhs.fields(),
+                rhs.fields()
+            ),
+            SchemaInferenceError::NonBooleanFilter(data_type) => {
+                write!(
+                    f,
+                    "Filter predicate has type {data_type:?} instead of Bool."
+                )
+            }
+            SchemaInferenceError::TypeMismatch(expr) => {
+                write!(f, "Operands of expression {expr} have incompatible types.")
+            }
+        }
+    }
+}
+
+impl Error for SchemaInferenceError {}
+
+type InferenceResult<T> = Result<T, SchemaInferenceError>;
+
+/// Returns the schema of the stream that is produced by the operators of `query`. `sources` maps
+/// the names of logical sources to their schemas.
+pub fn infer_schema(query: &Query, sources: &HashMap<String, Schema>) -> InferenceResult<Schema> {
+    let (_, schema) = infer_operator(query.operator(), sources)?;
+    Ok(schema)
+}
+
+/// Returns a copy of `query` in which every field of every expression and aggregation is typed
+/// according to the schemas of `sources`. Window keys are referred to by name and only checked to
+/// exist.
+pub fn infer_types(query: &Query, sources: &HashMap<String, Schema>) -> InferenceResult<Query> {
+    let (operator, _) = infer_operator(query.operator(), sources)?;
+    Ok(Query {
+        operator,
+        sink: query.sink().clone(),
+    })
+}
+
+fn infer_child(
+    child: &Option<Box<Operator>>,
+    sources: &HashMap<String, Schema>,
+) -> InferenceResult<(Option<Box<Operator>>, Schema)> {
+    let Some(child) = child else {
+        return Ok((None, Schema::new()));
+    };
+    let (operator, schema) = infer_operator(child, sources)?;
+    Ok((Some(Box::new(operator)), schema))
+}
+
+fn infer_operator(
+    operator: &Operator,
+    sources: &HashMap<String, Schema>,
+) -> InferenceResult<(Operator, Schema)> {
+    match operator {
+        Operator::InlineSource(source) => Ok((operator.clone(), source.schema.clone())),
+        Operator::LogicalSource { source_name, .. } => {
+            let Some(schema) = sources.get(source_name) else {
+                return Err(SchemaInferenceError::UnknownSource(source_name.clone()));
+            };
+            Ok((operator.clone(), schema.clone()))
+        }
+        Operator::Filter(filter) => infer_filter(filter, sources),
+        Operator::Projection(projection) => infer_projection(projection, sources),
+        Operator::Map(map) => infer_map(map, sources),
+        Operator::Window(window) => infer_window(window, sources),
+        Operator::Join(join) => infer_join(join, sources),
+        Operator::Union(union) => infer_union(union, sources),
+        Operator::Rename(rename) => infer_rename(rename, sources),
+        Operator::WatermarkAssigner(assigner) => {
+            let (child, schema) = infer_child(&assigner.child, sources)?;
+            if let WatermarkStrategy::EventTime { field_name, .. } = &assigner.strategy {
+                lookup_field(&schema, field_name)?;
+            }
+            let operator = Operator::WatermarkAssigner(WatermarkAssigner {
+                strategy: assigner.strategy.clone(),
+                child,
+            });
+            Ok((operator, schema))
+        }
+        Operator::Limit(limit) => {
+            let (child, schema) = infer_child(&limit.child, sources)?;
+            let operator = Operator::Limit(Limit {
+                limit: limit.limit,
+                child,
+            });
+            Ok((operator, schema))
+        }
+    }
+}
+
+fn infer_filter(
+    filter: &Filter,
+    sources: &HashMap<String, Schema>,
+) -> InferenceResult<(Operator, Schema)> {
+    let (child, schema) = infer_child(&filter.child, sources)?;
+    let expression = infer_expr(&filter.expression.0, &schema)?;
+    if !matches!(expression.data_type(), NesType::Bool | NesType::Undefined) {
+        return Err(SchemaInferenceError::NonBooleanFilter(
+            expression.data_type(),
+        ));
+    }
+    let operator = Operator::Filter(Filter {
+        expression: LogicalExpr(expression),
+        child,
+    });
+    Ok((operator, schema))
+}
+
+fn infer_projection(
+    projection: &Projection,
+    sources: &HashMap<String, Schema>,
+) -> InferenceResult<(Operator, Schema)> {
+    let (child, schema) = infer_child(&projection.child, sources)?;
+    let mut fields = Vec::new();
+    let mut output = Schema::new();
+    for field in &projection.fields {
+        let typed = infer_field(field, &schema)?;
+        output = output.with_field(
+            typed.projected_name().unwrap_or(typed.name()),
+            typed.data_type(),
+        );
+        fields.push(typed);
+    }
+    let operator = Operator::Projection(Projection { fields, child });
+    Ok((operator, output))
+}
+
+fn infer_map(map: &Map, sources: &HashMap<String, Schema>) -> InferenceResult<(Operator, Schema)> {
+    let (child, schema) = infer_child(&map.child, sources)?;
+    let expression = infer_expr(&map.expression.0, &schema)?;
+    let output = assign_field(&schema, &map.assigned_field, expression.data_type());
+    let operator = Operator::Map(Map {
+        assigned_field: map.assigned_field.clone(),
+        expression: ArithmeticExpr(expression),
+        child,
+    });
+    Ok((operator, output))
+}
+
+fn infer_window(
+    window: &Window,
+    sources: &HashMap<String, Schema>,
+) -> InferenceResult<(Operator, Schema)> {
+    let (child, schema) = infer_child(&window.child, sources)?;
+    let descriptor = infer_window_descriptor(&window.descriptor, &schema)?;
+    let mut output = window_bounds(qualifier(&schema).unwrap_or_default());
+    for key in window.key_fields.iter().flatten() {
+        let field = lookup_field(&schema, key)?;
+        output = output.with_field(key, field.data_type());
+    }
+    let mut aggregations = Vec::new();
+    for aggregation in &window.aggregations {
+        let (aggregation, input_type) = match aggregation.field() {
+            Some(field) => {
+                let typed = infer_field(field, &schema)?;
+                let input_type = typed.data_type();
+                (aggregation.clone().with_field(typed), input_type)
+            }
+            None => (aggregation.clone(), NesType::Undefined),
+        };
+        let data_type = match aggregation.agg_type() {
+            AggregationType::Count => NesType::Int(IntType::Unsigned64),
+            AggregationType::Average | AggregationType::Median => NesType::Float(FloatType::Bit64),
+            AggregationType::Sum | AggregationType::Min | AggregationType::Max => input_type,
+        };
+        let name = aggregation
+            .projected_field()
+            .or(aggregation.field())
+            .map_or("count", |field| field.name());
+        output = output.with_field(name, data_type);
+        aggregations.push(aggregation);
+    }
+    let operator = Operator::Window(Window {
+        descriptor,
+        aggregations,
+        key_fields: window.key_fields.clone(),
+        allowed_lateness: window.allowed_lateness.clone(),
+        child,
+    });
+    Ok((operator, output))
+}
+
+fn infer_join(
+    join: &Join,
+    sources: &HashMap<String, Schema>,
+) -> InferenceResult<(Operator, Schema)> {
+    let (child, lhs_schema) = infer_child(&join.child, sources)?;
+    let (joined_operators, rhs_schema) = infer_operator(&join.joined_operators, sources)?;
+    let lhs = infer_field(&join.lhs, &lhs_schema)?;
+    let rhs = infer_field(&join.rhs, &rhs_schema)?;
+    if NesType::try_resolve(lhs.data_type(), rhs.data_type()).is_none() {
+        return Err(SchemaInferenceError::TypeMismatch(format!(
+            "{} == {}",
+            lhs.name(),
+            rhs.name()
+        )));
+    }
+    let join_qualifier = [&lhs_schema, &rhs_schema]
+        .into_iter()
+        .filter_map(qualifier)
+        .collect::<String>();
+    let output = window_bounds(&join_qualifier)
+        .fields()
+        .iter()
+        .chain(lhs_schema.fields())
+        .chain(rhs_schema.fields())
+        .cloned()
+        .collect();
+    let operator = Operator::Join(Join {
+        lhs,
+        rhs,
+        window: join.window.clone(),
+        joined_operators: Box::new(joined_operators),
+        child,
+    });
+    Ok((operator, output))
+}
+
+fn infer_union(
+    union: &Union,
+    sources: &HashMap<String, Schema>,
+) -> InferenceResult<(Operator, Schema)> {
+    let (child, lhs) = infer_child(&union.child, sources)?;
+    let (operators, rhs) = infer_operator(&union.operators, sources)?;
+    // Both inputs may be qualified with different source names.
+    let equal_fields = lhs.len() == rhs.len()
+        && lhs.fields().iter().zip(rhs.fields()).all(|(lhs, rhs)| {
+            unqualified(lhs.name()) == unqualified(rhs.name()) && lhs.data_type() == rhs.data_type()
+        });
+    if !equal_fields {
+        return Err(SchemaInferenceError::UnionSchemaMismatch { lhs, rhs });
+    }
+    let operator = Operator::Union(Union {
+        operators: Box::new(operators),
+        child,
+    });
+    Ok((operator, lhs))
+}
+
+fn infer_rename(
+    rename: &Rename,
+    sources: &HashMap<String, Schema>,
+) -> InferenceResult<(Operator, Schema)> {
+    let (child, schema) = infer_child(&rename.child, sources)?;
+    let output = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let name = format!("{}${}", rename.new_source_name, unqualified(field.name()));
+            Field::typed(name, field.data_type())
+        })
+        .collect();
+    let operator = Operator::Rename(Rename {
+        new_source_name: rename.new_source_name.clone(),
+        child,
+    });
+    Ok((operator, output))
+}
+
+fn infer_window_descriptor(
+    descriptor: &WindowDescriptor,
+    schema: &Schema,
+) -> InferenceResult<WindowDescriptor> {
+    match descriptor {
+        WindowDescriptor::TumblingWindow { time_character, .. }
+        | WindowDescriptor::SlidingWindow { time_character, .. } => {
+            if let TimeCharacteristic::EventTime { field_name, .. } = time_character {
+                lookup_field(schema, field_name)?;
+            }
+            Ok(descriptor.clone())
+        }
+        WindowDescriptor::ThresholdWindow {
+            predicate,
+            minimum_count,
+        } => {
+            let predicate = infer_expr(&predicate.0, schema)?;
+            if !matches!(predicate.data_type(), NesType::Bool | NesType::Undefined) {
+                return Err(SchemaInferenceError::NonBooleanFilter(
+                    predicate.data_type(),
+                ));
+            }
+            Ok(WindowDescriptor::ThresholdWindow {
+                predicate: LogicalExpr(predicate),
+                minimum_count: *minimum_count,
+            })
+        }
+    }
+}
+
+/// Returns the expression with all fields typed according to `schema` and recomputes the types of
+/// all inner nodes.
+pub fn infer_expr(expr: &RawExpr, schema: &Schema) -> InferenceResult<RawExpr> {
+    match expr {
+        RawExpr::Literal(_) => Ok(expr.clone()),
+        RawExpr::Field(field) => Ok(RawExpr::Field(infer_field(field, schema)?)),
+        RawExpr::Unary(UnaryExpr {
+            expr: child,
+            operator,
+            ..
+        }) => {
+            let child = infer_expr(child, schema)?;
+            let Some(data_type) = operator.result_type(child.data_type()) else {
+                return Err(SchemaInferenceError::TypeMismatch(format!("{expr:?}")));
+            };
+            Ok(RawExpr::unary(child, *operator, data_type))
+        }
+        RawExpr::Binary(BinaryExpr {
+            lhs, rhs, operator, ..
+        }) => {
+            let lhs = infer_expr(lhs, schema)?;
+            let rhs = infer_expr(rhs, schema)?;
+            let resolved = NesType::try_resolve(lhs.data_type(), rhs.data_type());
+            let data_type = match (operator.get_op_type(), resolved) {
+                (BinaryOpType::Logical, Some(NesType::Bool | NesType::Undefined)) => NesType::Bool,
+                (BinaryOpType::Relational, Some(_)) => NesType::Bool,
+                (BinaryOpType::Arithmetic, Some(t @ (NesType::Int(_) | NesType::Float(_)))) => t,
+                (BinaryOpType::Arithmetic, Some(NesType::Undefined)) => NesType::Undefined,
+                _ => return Err(SchemaInferenceError::TypeMismatch(format!("{expr:?}"))),
+            };
+            Ok(RawExpr::binary(rhs, lhs, *operator, data_type))
+        }
+        RawExpr::Case(case) => {
+            let mut branches = Vec::new();
+            for branch in &case.branches {
+                let condition = infer_expr(&branch.condition, schema)?;
+                if !matches!(condition.data_type(), NesType::Bool | NesType::Undefined) {
+                    return Err(SchemaInferenceError::TypeMismatch(format!("{expr:?}")));
+                }
+                let value = infer_expr(&branch.value, schema)?;
+                branches.push(WhenBranch { condition, value });
+            }
+            let default = infer_expr(&case.default, schema)?;
+            let values = branches
+                .iter()
+                .map(|branch| &branch.value)
+                .chain(std::iter::once(&default));
+            let Some(data_type) = CaseExpr::resolve_type(values) else {
+                return Err(SchemaInferenceError::TypeMismatch(format!("{expr:?}")));
+            };
+            Ok(RawExpr::Case(CaseExpr {
+                branches,
+                default: Box::new(default),
+                data_type,
+            }))
+        }
+    }
+}
+
+fn infer_field(field: &Field, schema: &Schema) -> InferenceResult<Field> {
+    let data_type = lookup_field(schema, field.name())?.data_type();
+    let typed = Field::typed(field.name(), data_type);
+    Ok(match field.projected_name() {
+        Some(name) => typed.rename(name),
+        None => typed,
+    })
+}
+
+/// Finds the field `name` in `schema`. If there is no exact match, the `source$` qualifier of
+/// either name is ignored, e.g. `id` refers to `orders$id` unless the schema also has `products$id`.
+fn lookup_field<'a>(schema: &'a Schema, name: &str) -> InferenceResult<&'a Field> {
+    if let Some(field) = schema.field(name) {
+        return Ok(field);
+    }
+    let mut matches = schema.fields().iter().filter(|field| {
+        unqualified(field.name()) == unqualified(name)
+            && (!field.name().contains('$') || !name.contains('$'))
+    });
+    match (matches.next(), matches.next()) {
+        (Some(field), None) => Ok(field),
+        (Some(_), Some(_)) => Err(SchemaInferenceError::AmbiguousField(name.to_string())),
+        (None, _) => Err(SchemaInferenceError::UnknownField(name.to_string())),
+    }
+}
+
+fn unqualified(name: &str) -> &str {
+    name.rsplit('$').next().unwrap_or(name)
+}
+
+/// Replaces the type of `name` in `schema` or appends the field if it does not exist yet.
+fn assign_field(schema: &Schema, name: &str, data_type: NesType) -> Schema {
+    let name = lookup_field(schema, name).map_or(name, |field| field.name());
+    let mut output: Schema = schema
+        .fields()
+        .iter()
+        .map(|field| match field.name() == name {
+            true => Field::typed(name, data_type),
+            false => field.clone(),
+        })
+        .collect();
+    if !schema.contains(name) {
+        output = output.with_field(name, data_type);
+    }
+    output
+}
+
+/// Returns the `source$` qualifier of the first field of `schema`, if it is qualified.
+fn qualifier(schema: &Schema) -> Option<&str> {
+    let field = schema.fields().first()?;
+    field
+        .name()
+        .rsplit_once('$')
+        .map(|(qualifier, _)| qualifier)
+}
+
+/// The `start` and `end` fields generated by windows. Like the coordinator, the fields are
+/// qualified with the qualifier of the window's input, e.g. `orders$start`, and with the
+/// concatenated qualifiers of both inputs for joins, e.g. `ordersproducts$start`.
+fn window_bounds(qualifier: &str) -> Schema {
+    let name = |field: &str| match qualifier {
+        "" => field.to_string(),
+        _ => format!("{qualifier}${field}"),
+    };
+    Schema::new()
+        .with_field(name("start"), NesType::Int(IntType::Unsigned64))
+        .with_field(name("end"), NesType::Int(IntType::Unsigned64))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use nes_types::{IntType, NesType};
+
+    use super::{infer_schema, infer_types, SchemaInferenceError};
+    use crate::{
+        expression::{expression::RawExpr, ExprBuilder as EB, Field, LogicalExpr},
+        prelude::*,
+        query::{
+            operator::{Filter, Operator, Window},
+            time::{Duration, TimeCharacteristic},
+        },
+    };
+
+    fn sources() -> HashMap<String, Schema> {
+        let schema = Schema::new()
+            .with_field("id", NesType::Int(IntType::Unsigned64))
+            .with_field("value", NesType::Int(IntType::Signed32))
+            .with_field("valid", NesType::Bool);
+        let other = Schema::new().with_field("id", NesType::Int(IntType::Unsigned64));
+        HashMap::from([("test".to_string(), schema), ("other".to_string(), other)])
+    }
+
+    #[test]
+    fn test_filter_types() {
+        let query = QueryBuilder::from_source("test")
+            .filter(
+                EB::field("value")
+                    .greater_than(EB::literal(0))
+                    .build_logical()
+                    .unwrap(),
+            )
+            .sink(Sink::Print);
+        let typed = infer_types(&query, &sources()).unwrap();
+        let Operator::Filter(Filter { expression, .. }) = typed.operator() else {
+            panic!("Expected filter operator");
+        };
+        let fields = expression.0.leafs();
+        let expected: RawExpr = Field::typed("value", NesType::Int(IntType::Signed32)).into();
+        assert_eq!(expected, fields[0]);
+        assert_eq!(sources()["test"], infer_schema(&query, &sources()).unwrap());
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        let query = QueryBuilder::from_source("test")
+            .project([Field::untyped("missing")])
+            .sink(Sink::Print);
+        assert_eq!(
+            Err(SchemaInferenceError::UnknownField("missing".to_string())),
+            infer_schema(&query, &sources())
+        );
+        let query = QueryBuilder::from_source("missing").sink(Sink::Print);
+        assert!(infer_schema(&query, &sources()).is_err());
+    }
+
+    #[test]
+    fn test_non_boolean_filter() {
+        let query = QueryBuilder::from_source("test")
+            .filter(LogicalExpr(Field::untyped("value").into()))
+            .sink(Sink::Print);
+        assert_eq!(
+            Err(SchemaInferenceError::NonBooleanFilter(NesType::Int(
+                IntType::Signed32
+            ))),
+            infer_schema(&query, &sources())
+        );
+        let query = QueryBuilder::from_source("test")
+            .filter(
+                EB::field("valid")
+                    .and(EB::field("value"))
+                    .build_logical()
+                    .unwrap(),
+            )
+            .sink(Sink::Print);
+        assert!(infer_schema(&query, &sources()).is_err());
+    }
+
+    #[test]
+    fn test_union_mismatch() {
+        let query = QueryBuilder::from_source("test")
+            .union(QueryBuilder::from_source("other"))
+            .sink(Sink::Print);
+        assert!(matches!(
+            infer_schema(&query, &sources()),
+            Err(SchemaInferenceError::UnionSchemaMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_map_schema() {
+        let query = QueryBuilder::from_source("test")
+            .map(
+                "halved",
+                (EB::field("value") / EB::literal(2)).build_arith().unwrap(),
+            )
+            .sink(Sink::Print);
+        let schema = infer_schema(&query, &sources()).unwrap();
+        assert_eq!(4, schema.len());
+        assert_eq!(
+            NesType::Int(IntType::Signed32),
+            schema.field("halved").unwrap().data_type()
+        );
+    }
+
+    #[test]
+    fn test_typed_aggregations() {
+        let query = QueryBuilder::from_source("test")
+            .window(WindowDescriptor::tumbling(
+                Duration::from_seconds(1),
+                TimeCharacteristic::ingestion_time(),
+            ))
+            .by_key("id")
+            .apply([Aggregation::sum("value").as_field("total")])
+            .sink(Sink::Print);
+        let typed = infer_types(&query, &sources()).unwrap();
+        let Operator::Window(Window { aggregations, .. }) = typed.operator() else {
+            panic!("Expected window operator");
+        };
+        let field = aggregations[0].field().unwrap();
+        assert_eq!(NesType::Int(IntType::Signed32), field.data_type());
+        assert_eq!("total", aggregations[0].projected_field().unwrap().name());
+    }
+
+    #[test]
+    fn test_window_bounds() {
+        let window = |query: QueryBuilder| {
+            query
+                .window(WindowDescriptor::tumbling(
+                    Duration::from_seconds(1),
+                    TimeCharacteristic::ingestion_time(),
+                ))
+                .apply([Aggregation::count()])
+                .sink(Sink::Print)
+        };
+        let schema = infer_schema(&window(QueryBuilder::from_source("test")), &sources()).unwrap();
+        assert!(schema.contains("start") && schema.contains("end"));
+        let query = window(QueryBuilder::from_source("test").rename("orders"));
+        let schema = infer_schema(&query, &sources()).unwrap();
+        assert!(schema.contains("orders$start") && schema.contains("orders$end"));
+    }
+
+    #[test]
+    fn test_rename_self_join() {
+        let join = |predicate: &str| {
+            QueryBuilder::from_source("test")
+                .rename("lhs")
+                .join_with(QueryBuilder::from_source("test").rename("rhs"))
+                .where_field("id")
+                .equals("id")
+                .window(WindowDescriptor::tumbling(
+                    Duration::from_seconds(1),
+                    TimeCharacteristic::ingestion_time(),
+                ))
+                .filter(
+                    EB::field(predicate)
+                        .greater_than(EB::field("rhs$value"))
+                        .build_logical()
+                        .unwrap(),
+                )
+                .sink(Sink::Print)
+        };
+        let schema = infer_schema(&join("lhs$value"), &sources()).unwrap();
+        assert_eq!(8, schema.len());
+        assert!(schema.contains("lhs$id") && schema.contains("rhs$valid"));
+        assert!(schema.contains("lhsrhs$start") && schema.contains("lhsrhs$end"));
+        assert_eq!(
+            Err(SchemaInferenceError::AmbiguousField("value".to_string())),
+            infer_schema(&join("value"), &sources())
+        );
+
+        let query = QueryBuilder::from_source("test")
+            .rename("current")
+            .union(QueryBuilder::from_source("test").rename("archived"))
+            .sink(Sink::Print);
+        assert!(infer_schema(&query, &sources()).is_ok());
+    }
+}
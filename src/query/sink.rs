@@ -1,6 +1,6 @@
 use std::{fmt::Display, path::Path};
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Sink {
@@ -8,10 +8,147 @@ pub enum Sink {
     Print,
     File {
         path: String,
-        format: String,
+        format: SinkFormat,
         append: bool,
+        /// Adds the time at which a tuple is written as an additional column.
+        #[serde(default)]
+        add_timestamp: bool,
+    },
+    Kafka {
+        brokers: String,
+        topic: String,
+        /// Timeout for connecting to the brokers in milliseconds.
+        connect_timeout: u64,
+    },
+    Mqtt {
+        address: String,
+        client_id: String,
+        topic: String,
+        user: String,
+        max_buffered_messages: u64,
+        time_unit: MqttTimeUnit,
+        /// Delay between two messages in `time_unit`.
+        message_delay: u64,
+        quality_of_service: MqttQualityOfService,
+        asynchronous_client: bool,
+    },
+    Zmq {
+        host: String,
+        port: u32,
+    },
+    Opc {
+        url: String,
+        namespace_index: u32,
+        identifier: String,
+        identifier_type: OpcIdentifierType,
+        user: String,
+        password: Password,
     },
-    // TODO: Add the missing sinks
+}
+
+/// A password that is left out of the `Debug` output, e.g. when sinks are logged.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Password(String);
+
+impl Password {
+    pub fn new(password: impl Into<String>) -> Self {
+        Self(password.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for Password {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Password(***)")
+    }
+}
+
+/// The format in which a file sink writes tuples. Formats are serialized with the name the
+/// coordinator uses, e.g. `"CSV_FORMAT"`, which is also how older queries stored them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SinkFormat {
+    Csv,
+    Json,
+    /// The binary format of the NES buffers.
+    NesBinary,
+    Text,
+}
+
+impl SinkFormat {
+    /// Returns the name the coordinator uses for this format, e.g. `CSV_FORMAT`.
+    pub const fn as_coordinator_str(&self) -> &'static str {
+        match self {
+            SinkFormat::Csv => "CSV_FORMAT",
+            SinkFormat::Json => "JSON_FORMAT",
+            SinkFormat::NesBinary => "NES_FORMAT",
+            SinkFormat::Text => "TEXT_FORMAT",
+        }
+    }
+
+    /// Parses the name the coordinator uses for a format.
+    pub fn from_coordinator_str(format: &str) -> Option<Self> {
+        match format {
+            "CSV_FORMAT" => Some(SinkFormat::Csv),
+            "JSON_FORMAT" => Some(SinkFormat::Json),
+            "NES_FORMAT" => Some(SinkFormat::NesBinary),
+            "TEXT_FORMAT" => Some(SinkFormat::Text),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SinkFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_coordinator_str())
+    }
+}
+
+impl Serialize for SinkFormat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_coordinator_str())
+    }
+}
+
+/// Accepts the coordinator name of a format as well as the name of its variant, e.g. `Csv`.
+impl<'de> Deserialize<'de> for SinkFormat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        let format = match name.as_str() {
+            "Csv" => Some(SinkFormat::Csv),
+            "Json" => Some(SinkFormat::Json),
+            "NesBinary" => Some(SinkFormat::NesBinary),
+            "Text" => Some(SinkFormat::Text),
+            _ => SinkFormat::from_coordinator_str(&name),
+        };
+        format.ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(&name), &"a sink format"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MqttTimeUnit {
+    Nanoseconds,
+    Milliseconds,
+    Seconds,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MqttQualityOfService {
+    AtMostOnce,
+    AtLeastOnce,
+    ExactlyOnce,
+}
+
+/// The type of the identifier of the OPC node that results are written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpcIdentifierType {
+    Numeric,
+    String,
+    Guid,
+    ByteString,
 }
 
 impl Display for Sink {
@@ -19,7 +156,11 @@ impl Display for Sink {
         let sink_name = match self {
             Sink::NullOutput => "Null",
             Sink::Print => "Print",
-            Sink::File { .. } => "File(TODO!!!)",
+            Sink::File { .. } => "File",
+            Sink::Kafka { .. } => "Kafka",
+            Sink::Mqtt { .. } => "MQTT",
+            Sink::Zmq { .. } => "ZMQ",
+            Sink::Opc { .. } => "OPC",
         };
         write!(f, "{}", sink_name)
     }
@@ -36,11 +177,94 @@ impl Sink {
         Sink::Print
     }
 
-    pub fn csv_file<P: AsRef<Path>>(path: P, append: bool) -> Self {
+    pub fn file<P: AsRef<Path>>(path: P, format: SinkFormat, append: bool) -> Self {
         Sink::File {
             path: path.as_ref().to_string_lossy().into_owned(),
-            format: "CSV_FORMAT".to_string(),
+            format,
             append,
+            add_timestamp: false,
+        }
+    }
+
+    pub fn csv_file<P: AsRef<Path>>(path: P, append: bool) -> Self {
+        Sink::file(path, SinkFormat::Csv, append)
+    }
+
+    pub fn json_file<P: AsRef<Path>>(path: P, append: bool) -> Self {
+        Sink::file(path, SinkFormat::Json, append)
+    }
+
+    pub fn binary_file<P: AsRef<Path>>(path: P, append: bool) -> Self {
+        Sink::file(path, SinkFormat::NesBinary, append)
+    }
+
+    pub fn text_file<P: AsRef<Path>>(path: P, append: bool) -> Self {
+        Sink::file(path, SinkFormat::Text, append)
+    }
+
+    /// Makes a file sink add the time at which a tuple is written to each tuple. Other sinks are
+    /// returned unchanged.
+    pub fn with_timestamp(mut self) -> Self {
+        if let Sink::File { add_timestamp, .. } = &mut self {
+            *add_timestamp = true;
+        }
+        self
+    }
+
+    pub fn kafka(
+        brokers: impl Into<String>,
+        topic: impl Into<String>,
+        connect_timeout: u64,
+    ) -> Self {
+        Sink::Kafka {
+            brokers: brokers.into(),
+            topic: topic.into(),
+            connect_timeout,
+        }
+    }
+
+    /// Creates an MQTT sink without user that buffers up to 1000 messages and sends them with a
+    /// delay of 500 milliseconds using an asynchronous client.
+    pub fn mqtt(
+        address: impl Into<String>,
+        client_id: impl Into<String>,
+        topic: impl Into<String>,
+        quality_of_service: MqttQualityOfService,
+    ) -> Self {
+        Sink::Mqtt {
+            address: address.into(),
+            client_id: client_id.into(),
+            topic: topic.into(),
+            user: String::new(),
+            max_buffered_messages: 1000,
+            time_unit: MqttTimeUnit::Milliseconds,
+            message_delay: 500,
+            quality_of_service,
+            asynchronous_client: true,
+        }
+    }
+
+    pub fn zmq(host: impl Into<String>, port: u32) -> Self {
+        Sink::Zmq {
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// Creates an OPC sink that connects without credentials.
+    pub fn opc(
+        url: impl Into<String>,
+        namespace_index: u32,
+        identifier: impl Into<String>,
+        identifier_type: OpcIdentifierType,
+    ) -> Self {
+        Sink::Opc {
+            url: url.into(),
+            namespace_index,
+            identifier: identifier.into(),
+            identifier_type,
+            user: String::new(),
+            password: Password::default(),
         }
     }
 }
@@ -49,7 +273,9 @@ impl Sink {
 mod tests {
     use std::path::PathBuf;
 
-    use super::Sink;
+    use super::{
+        MqttQualityOfService, MqttTimeUnit, OpcIdentifierType, Password, Sink, SinkFormat,
+    };
 
     #[test]
     fn test_csv_file_sink() {
@@ -59,10 +285,77 @@ mod tests {
         let sink1 = Sink::csv_file(path, true);
         let expected_sink = Sink::File {
             path: "result.log".to_string(),
-            format: "CSV_FORMAT".to_string(),
+            format: SinkFormat::Csv,
             append: true,
+            add_timestamp: false,
         };
         assert_eq!(expected_sink, sink0);
         assert_eq!(expected_sink, sink1);
     }
+
+    #[test]
+    fn test_file_sink_formats() {
+        let sink = Sink::json_file("result.json", false).with_timestamp();
+        let expected_sink = Sink::File {
+            path: "result.json".to_string(),
+            format: SinkFormat::Json,
+            append: false,
+            add_timestamp: true,
+        };
+        assert_eq!(expected_sink, sink);
+        let Sink::File { format, .. } = Sink::binary_file("result.bin", false) else {
+            panic!("expected file sink");
+        };
+        assert_eq!("NES_FORMAT", format.as_coordinator_str());
+        assert_eq!(Some(format), SinkFormat::from_coordinator_str("NES_FORMAT"));
+        assert_eq!(None, SinkFormat::from_coordinator_str("CSV"));
+    }
+
+    #[test]
+    fn test_file_sink_serde() {
+        let legacy = r#"File(path: "result.csv", format: "CSV_FORMAT", append: true)"#;
+        let sink: Sink = ron::from_str(legacy).unwrap();
+        assert_eq!(Sink::csv_file("result.csv", true), sink);
+        let ron = ron::to_string(&sink).unwrap();
+        assert_eq!(sink, ron::from_str(&ron).unwrap());
+        let sink: Sink = serde_json::from_str(
+            r#"{"File": {"path": "result.json", "format": "Json", "append": false}}"#,
+        )
+        .unwrap();
+        assert_eq!(Sink::json_file("result.json", false), sink);
+        assert!(ron::from_str::<Sink>(r#"File(path: "a", format: "CSV", append: true)"#).is_err());
+    }
+
+    #[test]
+    fn test_opc_password_is_redacted() {
+        let mut sink = Sink::opc(
+            "opc.tcp://localhost:4840",
+            1,
+            "results",
+            OpcIdentifierType::Guid,
+        );
+        if let Sink::Opc { password, .. } = &mut sink {
+            *password = Password::new("secret");
+        }
+        assert!(!format!("{sink:?}").contains("secret"));
+        let ron = ron::to_string(&sink).unwrap();
+        assert!(ron.contains("password:\"secret\""));
+        assert_eq!(sink, ron::from_str(&ron).unwrap());
+    }
+
+    #[test]
+    fn test_mqtt_sink_serde() {
+        let sink = Sink::mqtt(
+            "tcp://localhost:1883",
+            "nes",
+            "results",
+            MqttQualityOfService::ExactlyOnce,
+        );
+        let Sink::Mqtt { time_unit, .. } = &sink else {
+            panic!("expected MQTT sink");
+        };
+        assert_eq!(MqttTimeUnit::Milliseconds, *time_unit);
+        let ron = ron::to_string(&sink).unwrap();
+        assert_eq!(sink, ron::from_str(&ron).unwrap());
+    }
 }
@@ -0,0 +1,197 @@
+use std::{fmt::Display, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::schema::Schema;
+
+/// A source that is defined as part of the query instead of being registered with the
+/// coordinator, e.g. a CSV file with a given schema. The schema is sent to the coordinator
+/// qualified with `name`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InlineSource {
+    pub name: String,
+    pub schema: Schema,
+    pub descriptor: SourceDescriptor,
+}
+
+/// The format of the data read by MQTT and TCP sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputFormat {
+    Json,
+    Csv,
+    NesBinary,
+}
+
+/// Describes where and how a source reads its data. Counts and intervals of `0` let the worker
+/// decide, e.g. a CSV source with `number_of_buffers_to_produce: 0` reads the whole file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SourceDescriptor {
+    Csv {
+        path: String,
+        skip_header: bool,
+        delimiter: String,
+        number_of_buffers_to_produce: u32,
+        number_of_tuples_to_produce_per_buffer: u32,
+        /// Interval between two buffers in milliseconds.
+        gathering_interval: u32,
+    },
+    Binary {
+        path: String,
+    },
+    /// Produces buffers of generated tuples, mainly used for testing.
+    Default {
+        number_of_buffers_to_produce: u64,
+        /// Interval between two buffers in milliseconds.
+        gathering_interval: u64,
+    },
+    Kafka {
+        brokers: String,
+        topic: String,
+        group_id: String,
+        auto_commit: bool,
+        /// Timeout for connecting to the brokers in milliseconds.
+        connect_timeout: u64,
+    },
+    Mqtt {
+        url: String,
+        client_id: String,
+        user: String,
+        topic: String,
+        quality_of_service: u32,
+        clean_session: bool,
+        flush_interval_ms: f32,
+        input_format: InputFormat,
+    },
+    /// Reads tuples separated by `tuple_separator` from a TCP socket.
+    Tcp {
+        host: String,
+        port: u32,
+        input_format: InputFormat,
+        tuple_separator: String,
+        flush_interval_ms: f32,
+    },
+    Zmq {
+        host: String,
+        port: u32,
+    },
+    Arrow {
+        path: String,
+        number_of_buffers_to_produce: u32,
+        number_of_tuples_to_produce_per_buffer: u32,
+        /// Interval between two buffers in milliseconds.
+        gathering_interval: u32,
+    },
+}
+
+impl SourceDescriptor {
+    /// Reads a whole CSV file with a header, using `,` as delimiter.
+    pub fn csv<P: AsRef<Path>>(path: P) -> Self {
+        SourceDescriptor::Csv {
+            path: path.as_ref().to_string_lossy().into_owned(),
+            skip_header: true,
+            delimiter: ",".to_string(),
+            number_of_buffers_to_produce: 0,
+            number_of_tuples_to_produce_per_buffer: 0,
+            gathering_interval: 0,
+        }
+    }
+
+    pub fn binary<P: AsRef<Path>>(path: P) -> Self {
+        SourceDescriptor::Binary {
+            path: path.as_ref().to_string_lossy().into_owned(),
+        }
+    }
+
+    pub fn default_source(number_of_buffers_to_produce: u64) -> Self {
+        SourceDescriptor::Default {
+            number_of_buffers_to_produce,
+            gathering_interval: 0,
+        }
+    }
+
+    /// Consumes `topic` with automatic commits and a connect timeout of 10 seconds.
+    pub fn kafka(
+        brokers: impl Into<String>,
+        topic: impl Into<String>,
+        group_id: impl Into<String>,
+    ) -> Self {
+        SourceDescriptor::Kafka {
+            brokers: brokers.into(),
+            topic: topic.into(),
+            group_id: group_id.into(),
+            auto_commit: true,
+            connect_timeout: 10000,
+        }
+    }
+
+    /// Subscribes to `topic` without user, with quality of service 1 and a clean session.
+    pub fn mqtt(
+        url: impl Into<String>,
+        client_id: impl Into<String>,
+        topic: impl Into<String>,
+        input_format: InputFormat,
+    ) -> Self {
+        SourceDescriptor::Mqtt {
+            url: url.into(),
+            client_id: client_id.into(),
+            user: String::new(),
+            topic: topic.into(),
+            quality_of_service: 1,
+            clean_session: true,
+            flush_interval_ms: 0.0,
+            input_format,
+        }
+    }
+
+    /// Reads newline separated tuples.
+    pub fn tcp(host: impl Into<String>, port: u32, input_format: InputFormat) -> Self {
+        SourceDescriptor::Tcp {
+            host: host.into(),
+            port,
+            input_format,
+            tuple_separator: "\n".to_string(),
+            flush_interval_ms: 0.0,
+        }
+    }
+
+    pub fn zmq(host: impl Into<String>, port: u32) -> Self {
+        SourceDescriptor::Zmq {
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// Reads a whole Arrow IPC file.
+    pub fn arrow<P: AsRef<Path>>(path: P) -> Self {
+        SourceDescriptor::Arrow {
+            path: path.as_ref().to_string_lossy().into_owned(),
+            number_of_buffers_to_produce: 0,
+            number_of_tuples_to_produce_per_buffer: 0,
+            gathering_interval: 0,
+        }
+    }
+}
+
+impl Display for SourceDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceDescriptor::Csv { path, .. } => write!(f, "CSVSource(\"{path}\")"),
+            SourceDescriptor::Binary { path } => write!(f, "BinarySource(\"{path}\")"),
+            SourceDescriptor::Default {
+                number_of_buffers_to_produce,
+                ..
+            } => write!(f, "DefaultSource({number_of_buffers_to_produce})"),
+            SourceDescriptor::Kafka { brokers, topic, .. } => {
+                write!(f, "KafkaSource(\"{brokers}\", \"{topic}\")")
+            }
+            SourceDescriptor::Mqtt { url, topic, .. } => {
+                write!(f, "MQTTSource(\"{url}\", \"{topic}\")")
+            }
+            SourceDescriptor::Tcp { host, port, .. } => {
+                write!(f, "TCPSource(\"{host}\", {port})")
+            }
+            SourceDescriptor::Zmq { host, port } => write!(f, "ZMQSource(\"{host}\", {port})"),
+            SourceDescriptor::Arrow { path, .. } => write!(f, "ArrowSource(\"{path}\")"),
+        }
+    }
+}
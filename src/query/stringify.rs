@@ -1,15 +1,18 @@
 // this is for pretty printing of queries and expressions
 
+use nes_types::{FloatType, IntType, NesType};
+
 use super::{
     join::Join,
     operator::{Filter, Map, Operator, Projection, Union, Window},
     sink::Sink,
-    time::{Duration, TimeCharacteristic, TimeUnit},
+    time::{Duration, TimeCharacteristic, TimeUnit, WatermarkStrategy},
     window::{aggregation::Aggregation, window_descriptor::WindowDescriptor},
     Query,
 };
 use crate::expression::{
     binary_expression::{BinaryExpr, BinaryOp},
+    case_expression::CaseExpr,
     expression::RawExpr,
     literal::Literal,
     unary_expression::{UnaryExpr, UnaryOp},
@@ -33,13 +36,47 @@ pub fn stringify_query(query: &Query) -> String {
 fn stringify_operator(operator: Option<&Operator>) -> String {
     use Operator as O;
     match operator {
-        Some(O::LogicalSource { source_name }) => format!("logical_source(\"{source_name}\")"),
+        Some(O::LogicalSource {
+            source_name,
+            physical_source_name: None,
+        }) => format!("logical_source({})", quote(source_name)),
+        Some(O::LogicalSource {
+            source_name,
+            physical_source_name: Some(physical_source_name),
+        }) => format!(
+            "logical_source({}, {})",
+            quote(source_name),
+            quote(physical_source_name)
+        ),
+        // Only a summary of the descriptor is printed, inline sources can not be parsed.
+        Some(O::InlineSource(source)) => {
+            format!(
+                "inline_source({}, {})",
+                quote(&source.name),
+                source.descriptor
+            )
+        }
         Some(O::Filter(filter)) => stringify_filter_operator(filter),
         Some(O::Projection(projection)) => stringify_projection_operator(projection),
         Some(O::Map(map)) => stringify_map_operator(map),
         Some(O::Window(window)) => stringify_window_operator(window),
         Some(O::Join(join)) => stringify_join_operator(join),
         Some(O::Union(union)) => stringify_union_operator(union),
+        Some(O::WatermarkAssigner(assigner)) => format!(
+            "{}.assign_watermark({})",
+            stringify_operator(assigner.child.as_deref()),
+            stringify_watermark_strategy(&assigner.strategy)
+        ),
+        Some(O::Limit(limit)) => format!(
+            "{}.limit({})",
+            stringify_operator(limit.child.as_deref()),
+            limit.limit
+        ),
+        Some(O::Rename(rename)) => format!(
+            "{}.as({})",
+            stringify_operator(rename.child.as_deref()),
+            quote(&rename.new_source_name)
+        ),
         None => String::new(),
     }
 }
@@ -57,10 +94,14 @@ fn stringify_projection_operator(projection: &Projection) -> String {
     for field in &projection.fields {
         let field_name = field.name();
         if let Some(as_name) = field.projected_name() {
-            fields.push_str(&format!("Field(\"{field_name}\").as(\"{as_name}\"), "));
+            fields.push_str(&format!(
+                "Field({}).as({}), ",
+                quote(field_name),
+                quote(as_name)
+            ));
             continue;
         }
-        fields.push_str(&format!("Field(\"{field_name}\"), "));
+        fields.push_str(&format!("Field({}), ", quote(field_name)));
     }
     format!(
         "{}.project({fields})",
@@ -72,15 +113,27 @@ fn stringify_map_operator(map: &Map) -> String {
     format!(
         "{}.map({}, {})",
         stringify_operator(map.child.as_deref()),
-        map.assigned_field,
+        quote(&map.assigned_field),
         stringify_expr(&map.expression.0)
     )
 }
 
 // FIXME: Window
 fn stringify_window_operator(window: &Window) -> String {
+    let keys: String = window
+        .key_fields
+        .iter()
+        .flatten()
+        .map(|key| format!(".by_key({})", quote(key)))
+        .collect();
+    let allowed_lateness = window
+        .allowed_lateness
+        .as_ref()
+        .map_or(String::new(), |lateness| {
+            format!(".allowed_lateness({})", stringify_duration(lateness))
+        });
     format!(
-        "{}.window({}).apply({})",
+        "{}.window({}){keys}{allowed_lateness}.apply({})",
         stringify_operator(window.child.as_deref()),
         stringify_window_descriptor(&window.descriptor),
         stringify_aggregations(&window.aggregations),
@@ -97,11 +150,32 @@ fn stringify_window_descriptor(desr: &WindowDescriptor) -> String {
             stringify_duration(duration),
             stringify_time_character(time_character)
         ),
+        WindowDescriptor::SlidingWindow {
+            size,
+            slide,
+            time_character,
+        } => format!(
+            "SlidingWindow({}, {}, {})",
+            stringify_duration(size),
+            stringify_duration(slide),
+            stringify_time_character(time_character)
+        ),
+        WindowDescriptor::ThresholdWindow {
+            predicate,
+            minimum_count,
+        } => format!(
+            "ThresholdWindow({}, {minimum_count})",
+            stringify_expr(&predicate.0)
+        ),
     }
 }
 
 fn stringify_duration(duration: &Duration) -> String {
-    format!("{}{}", duration.amount, stringify_time_unit_symbol(&duration.unit))
+    format!(
+        "{}{}",
+        duration.amount,
+        stringify_time_unit_symbol(&duration.unit)
+    )
 }
 
 fn stringify_time_unit_symbol(unit: &TimeUnit) -> String {
@@ -111,7 +185,8 @@ fn stringify_time_unit_symbol(unit: &TimeUnit) -> String {
         TimeUnit::Minutes => "min",
         TimeUnit::Hours => "h",
         TimeUnit::Days => "d",
-    }.to_string()
+    }
+    .to_string()
 }
 
 fn stringify_time_unit(unit: &TimeUnit) -> String {
@@ -121,14 +196,36 @@ fn stringify_time_unit(unit: &TimeUnit) -> String {
         TimeUnit::Minutes => "Minutes",
         TimeUnit::Hours => "Hours",
         TimeUnit::Days => "Days",
-    }.to_string()
+    }
+    .to_string()
+}
+
+fn stringify_watermark_strategy(strategy: &WatermarkStrategy) -> String {
+    match strategy {
+        WatermarkStrategy::EventTime {
+            field_name,
+            allowed_lateness,
+            unit,
+        } => format!(
+            "EventTimeWatermark({}, {}, {})",
+            quote(field_name),
+            stringify_duration(allowed_lateness),
+            stringify_time_unit(unit)
+        ),
+        WatermarkStrategy::IngestionTime => "IngestionTimeWatermark()".to_string(),
+    }
 }
 
 fn stringify_time_character(character: &TimeCharacteristic) -> String {
     match character {
         TimeCharacteristic::EventTime { field_name, unit } => {
-            format!("EventTime(\"{field_name}\", {})", stringify_time_unit(unit))
+            format!(
+                "EventTime({}, {})",
+                quote(field_name),
+                stringify_time_unit(unit)
+            )
         }
+        TimeCharacteristic::IngestionTime => "IngestionTime()".to_string(),
     }
 }
 
@@ -149,12 +246,12 @@ fn stringify_aggregations(aggregations: &[Aggregation]) -> String {
         };
         let agg_str = match (field, projected_field) {
             (None, None) => format!("{agg_type_str}(), "),
-            (None, Some(p)) => format!("{agg_type_str}(as(\"{}\")), ", p.name()),
-            (Some(f), None) => format!("{agg_type_str}(Field(\"{}\")), ", f.name()),
+            (None, Some(p)) => format!("{agg_type_str}(as({})), ", quote(p.name())),
+            (Some(f), None) => format!("{agg_type_str}(Field({})), ", quote(f.name())),
             (Some(f), Some(p)) => format!(
-                "{agg_type_str}(Field(\"{}\").as(\"{}\")), ",
-                f.name(),
-                p.name()
+                "{agg_type_str}(Field({}).as({})), ",
+                quote(f.name()),
+                quote(p.name())
             ),
         };
         result.push_str(&agg_str);
@@ -164,9 +261,12 @@ fn stringify_aggregations(aggregations: &[Aggregation]) -> String {
 
 fn stringify_join_operator(join: &Join) -> String {
     format!(
-        "{}.join({})",
+        "{}.join_with({}).where_field({}).equals({}).window({})",
         stringify_operator(join.child.as_deref()),
-        "TODO!!!"
+        stringify_operator(Some(&join.joined_operators)),
+        stringify_field_expr(&join.lhs),
+        stringify_field_expr(&join.rhs),
+        stringify_window_descriptor(&join.window),
     )
 }
 
@@ -184,21 +284,47 @@ pub fn stringify_expr(expr: &RawExpr) -> String {
         RawExpr::Field(field) => stringify_field_expr(field),
         RawExpr::Unary(unary) => stringify_unary_expr(unary),
         RawExpr::Binary(binary) => stringify_binary_expr(binary),
+        RawExpr::Case(case) => stringify_case_expr(case),
     }
 }
 
+/// Appends a type suffix like `i64` to numbers whose type the parser would not infer by itself.
 fn stringify_literal_expr(literal: &Literal) -> String {
-    format!("{}", literal.value())
+    let value = literal.value();
+    let suffix = match literal.data_type() {
+        NesType::Int(IntType::Signed8) => "i8",
+        NesType::Int(IntType::Signed16) => "i16",
+        NesType::Int(IntType::Signed32) => "",
+        NesType::Int(IntType::Signed64) => "i64",
+        NesType::Int(IntType::Unsigned8) => "u8",
+        NesType::Int(IntType::Unsigned16) => "u16",
+        NesType::Int(IntType::Unsigned32) => "u32",
+        NesType::Int(IntType::Unsigned64) => "u64",
+        NesType::Float(FloatType::Bit32) => "f32",
+        NesType::Float(FloatType::Bit64) if !value.contains('.') => "f64",
+        _ => "",
+    };
+    format!("{value}{suffix}")
+}
+
+/// Writes `value` as a string literal the parser accepts, i.e. escapes backslashes and quotes.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
 }
 
 fn stringify_field_expr(field: &Field) -> String {
-    format!("Field(\"{}\")", field.name())
+    format!("Field({})", quote(field.name()))
 }
 
 fn stringify_unary_expr(expr: &UnaryExpr) -> String {
     let op = match expr.operator {
         UnaryOp::Negate => "!",
         UnaryOp::Absolute => "abs",
+        UnaryOp::Ceil => "ceil",
+        UnaryOp::Floor => "floor",
+        UnaryOp::Round => "round",
+        UnaryOp::Sqrt => "sqrt",
+        UnaryOp::Exp => "exp",
     };
     format!("{op}({})", stringify_expr(&expr.expr))
 }
@@ -219,7 +345,7 @@ fn stringify_binary_expr(expr: &BinaryExpr) -> String {
         BinaryOp::Multiply => "*",
         BinaryOp::Divide => "/",
         BinaryOp::Remainder => "%",
-        BinaryOp::Power => "^"
+        BinaryOp::Power => "^",
     };
     format!(
         "({} {op} {})",
@@ -228,6 +354,18 @@ fn stringify_binary_expr(expr: &BinaryExpr) -> String {
     )
 }
 
+fn stringify_case_expr(expr: &CaseExpr) -> String {
+    let mut branches = String::new();
+    for branch in &expr.branches {
+        branches.push_str(&format!(
+            "when({}, {}), ",
+            stringify_expr(&branch.condition),
+            stringify_expr(&branch.value)
+        ));
+    }
+    format!("case({branches}{})", stringify_expr(&expr.default))
+}
+
 pub fn stringify_sink(sink: &Sink) -> String {
     match sink {
         Sink::NullOutput => format!("NullOutputSink"),
@@ -236,6 +374,52 @@ pub fn stringify_sink(sink: &Sink) -> String {
             path,
             format,
             append,
-        } => format!("FileSink(\"{path}\", {format}, {append})"),
+            add_timestamp: false,
+        } => format!("FileSink({}, {format}, {append})", quote(path)),
+        Sink::File {
+            path,
+            format,
+            append,
+            add_timestamp: true,
+        } => format!("FileSink({}, {format}, {append}, true)", quote(path)),
+        Sink::Kafka {
+            brokers,
+            topic,
+            connect_timeout,
+        } => format!(
+            "KafkaSink({}, {}, {connect_timeout})",
+            quote(brokers),
+            quote(topic)
+        ),
+        Sink::Mqtt {
+            address,
+            client_id,
+            topic,
+            user,
+            max_buffered_messages,
+            time_unit,
+            message_delay,
+            quality_of_service,
+            asynchronous_client,
+        } => format!(
+            "MQTTSink({}, {}, {}, {}, {max_buffered_messages}, {time_unit:?}, {message_delay}, {quality_of_service:?}, {asynchronous_client})",
+            quote(address),
+            quote(client_id),
+            quote(topic),
+            quote(user)
+        ),
+        Sink::Zmq { host, port } => format!("ZMQSink({}, {port})", quote(host)),
+        // Credentials are left out since stringified queries end up in logs.
+        Sink::Opc {
+            url,
+            namespace_index,
+            identifier,
+            identifier_type,
+            ..
+        } => format!(
+            "OPCSink({}, {namespace_index}, {}, {identifier_type:?})",
+            quote(url),
+            quote(identifier)
+        ),
     }
 }
@@ -1,11 +1,60 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TimeCharacteristic {
     EventTime { field_name: String, unit: TimeUnit },
+    /// Windows are assigned by the time a tuple arrives at the source. Use this for sources that
+    /// have no timestamp field.
+    IngestionTime,
 }
 
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+impl TimeCharacteristic {
+    pub fn event_time(field_name: impl Into<String>, unit: TimeUnit) -> Self {
+        TimeCharacteristic::EventTime {
+            field_name: field_name.into(),
+            unit,
+        }
+    }
+
+    pub fn ingestion_time() -> Self {
+        TimeCharacteristic::IngestionTime
+    }
+}
+
+/// Determines how the watermarks of a stream are computed. Windows are only closed once the
+/// watermark passed their end.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WatermarkStrategy {
+    /// The watermark follows the timestamps in `field_name`, which are given in `unit`, delayed by
+    /// `allowed_lateness`. Tuples arriving at most `allowed_lateness` late are still processed.
+    EventTime {
+        field_name: String,
+        allowed_lateness: Duration,
+        unit: TimeUnit,
+    },
+    /// The watermark follows the time tuples arrive at the source.
+    IngestionTime,
+}
+
+impl WatermarkStrategy {
+    pub fn event_time(
+        field_name: impl Into<String>,
+        allowed_lateness: Duration,
+        unit: TimeUnit,
+    ) -> Self {
+        WatermarkStrategy::EventTime {
+            field_name: field_name.into(),
+            allowed_lateness,
+            unit,
+        }
+    }
+
+    pub fn ingestion_time() -> Self {
+        WatermarkStrategy::IngestionTime
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TimeUnit {
     #[default]
     Milliseconds,
@@ -38,7 +87,7 @@ impl TimeUnit {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Duration {
     pub amount: u32,
     pub unit: TimeUnit,
@@ -73,6 +73,12 @@ impl Aggregation {
         self
     }
 
+    /// Replaces the aggregated field, e.g. with a typed version of it.
+    pub fn with_field(mut self, field: Field) -> Self {
+        self.field = Some(field);
+        self
+    }
+
     pub fn field(&self) -> Option<&Field> {
         self.field.as_ref()
     }
@@ -1,20 +1,98 @@
 use serde::{Deserialize, Serialize};
 
+use crate::expression::LogicalExpr;
 use crate::query::time::{Duration, TimeCharacteristic};
 
-#[derive(Debug, Clone, Serialize, Deserialize) ]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize) ]
 pub enum WindowDescriptor {
     TumblingWindow {
         duration: Duration,
         time_character: TimeCharacteristic,
     },
-    // SlidingWindow {
-    //     time_character: TimeCharacteristic,
-    //     size: Duration,
-    //     slide: Duration,
-    // },
-    // ThresholdWindow {
-    //     duration: Duration,
-    //     time_character: TimeCaracteristic,
-    // },
+    SlidingWindow {
+        size: Duration,
+        slide: Duration,
+        time_character: TimeCharacteristic,
+    },
+    ThresholdWindow {
+        predicate: LogicalExpr,
+        minimum_count: u32,
+    },
+}
+
+impl WindowDescriptor {
+    // Constructors
+
+    pub fn tumbling(duration: Duration, time_character: TimeCharacteristic) -> Self {
+        WindowDescriptor::TumblingWindow {
+            duration,
+            time_character,
+        }
+    }
+
+    /// Creates a window of length `size` that is started every `slide`. Windows overlap if
+    /// `slide` is smaller than `size`.
+    pub fn sliding(size: Duration, slide: Duration, time_character: TimeCharacteristic) -> Self {
+        WindowDescriptor::SlidingWindow {
+            size,
+            slide,
+            time_character,
+        }
+    }
+
+    /// Creates a window that is open as long as `predicate` holds. Windows that contain less than
+    /// `minimum_count` tuples are discarded.
+    pub fn threshold(predicate: LogicalExpr, minimum_count: u32) -> Self {
+        WindowDescriptor::ThresholdWindow {
+            predicate,
+            minimum_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::ExprBuilder as EB,
+        query::time::{Duration, TimeCharacteristic, TimeUnit},
+    };
+
+    use super::WindowDescriptor;
+
+    #[test]
+    fn test_ron_round_trip() {
+        let descriptors = [
+            WindowDescriptor::tumbling(
+                Duration::from_seconds(10),
+                TimeCharacteristic::EventTime {
+                    field_name: "ts".to_string(),
+                    unit: TimeUnit::Milliseconds,
+                },
+            ),
+            WindowDescriptor::sliding(
+                Duration::from_minutes(10),
+                Duration::from_minutes(1),
+                TimeCharacteristic::EventTime {
+                    field_name: "ts".to_string(),
+                    unit: TimeUnit::Seconds,
+                },
+            ),
+            WindowDescriptor::tumbling(
+                Duration::from_seconds(10),
+                TimeCharacteristic::ingestion_time(),
+            ),
+            WindowDescriptor::threshold(
+                EB::field("value")
+                    .greater_than(EB::literal(100))
+                    .build_logical()
+                    .unwrap(),
+                5,
+            ),
+        ];
+        for descriptor in descriptors {
+            let ron_str = ron::to_string(&descriptor).unwrap();
+            let deser: WindowDescriptor = ron::from_str(&ron_str).unwrap();
+            assert_eq!(descriptor, deser);
+        }
+    }
 }
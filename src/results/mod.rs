@@ -0,0 +1,3 @@
+pub mod result_error;
+pub mod result_reader;
+pub mod row;
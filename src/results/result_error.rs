@@ -0,0 +1,68 @@
+use std::{error::Error, fmt::Display};
+
+use nes_types::NesType;
+
+/// Errors returned by the `ResultReader` when reading the output of a file sink.
+#[derive(Debug)]
+pub enum ResultError {
+    /// The result file could not be read.
+    Io(std::io::Error),
+    /// The sink does not write to a file in a readable format.
+    UnsupportedSink(String),
+    /// A field of the output schema is not contained in the result file.
+    MissingField { line: usize, field: String },
+    /// A line of the result file does not match the output schema.
+    InvalidLine { line: usize, message: String },
+    /// A value can not be parsed as the type of its field.
+    InvalidValue {
+        line: usize,
+        field: String,
+        value: String,
+        data_type: NesType,
+    },
+    /// The expected number of rows was not written in time.
+    Timeout { expected: usize, received: usize },
+}
+
+impl Display for ResultError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResultError::Io(err) => write!(f, "Unable to read results: {err}"),
+            ResultError::UnsupportedSink(sink) => {
+                write!(f, "Unable to read the results of sink {sink}.")
+            }
+            ResultError::MissingField { line, field } => {
+                write!(f, "Line {line} does not contain the field {field}.")
+            }
+            ResultError::InvalidLine { line, message } => write!(f, "Line {line}: {message}."),
+            ResultError::InvalidValue {
+                line,
+                field,
+                value,
+                data_type,
+            } => write!(
+                f,
+                "Line {line}: Unable to parse {value} of field {field} as {data_type:?}."
+            ),
+            ResultError::Timeout { expected, received } => write!(
+                f,
+                "Expected {expected} rows but only {received} were written in time."
+            ),
+        }
+    }
+}
+
+impl Error for ResultError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ResultError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ResultError {
+    fn from(value: std::io::Error) -> Self {
+        ResultError::Io(value)
+    }
+}
@@ -0,0 +1,383 @@
+use std::{
+    fs::File,
+    io::{ErrorKind, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde_json::Value;
+use tokio::time::Instant;
+
+use crate::{
+    expression::Field,
+    query::{
+        schema::Schema,
+        sink::{Sink, SinkFormat},
+    },
+};
+
+use super::{
+    result_error::ResultError,
+    row::{FieldValue, Row},
+};
+
+/// Reads the rows a file sink writes in CSV or JSON format. The output schema of the query, e.g.
+/// from `infer_schema`, determines the names and types of the fields. Fields are matched by name,
+/// with or without the `source$` prefix the coordinator adds.
+///
+/// The reader remembers how far it has read, so `read_new` can be called repeatedly while the
+/// query is still running. Only complete lines are read.
+#[derive(Debug)]
+pub struct ResultReader {
+    path: PathBuf,
+    format: SinkFormat,
+    schema: Schema,
+    offset: u64,
+    line: usize,
+    /// For each field of the schema the index of its CSV column. `None` until the first line
+    /// has been read.
+    columns: Option<Vec<usize>>,
+}
+
+impl ResultReader {
+    pub fn new(
+        path: impl AsRef<Path>,
+        format: SinkFormat,
+        schema: Schema,
+    ) -> Result<Self, ResultError> {
+        if !matches!(format, SinkFormat::Csv | SinkFormat::Json) {
+            return Err(ResultError::UnsupportedSink(format!(
+                "with format {format}"
+            )));
+        }
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            format,
+            schema,
+            offset: 0,
+            line: 0,
+            columns: None,
+        })
+    }
+
+    /// Creates a reader for the file a `Sink::File` writes to.
+    pub fn from_sink(sink: &Sink, schema: Schema) -> Result<Self, ResultError> {
+        let Sink::File { path, format, .. } = sink else {
+            return Err(ResultError::UnsupportedSink(sink.to_string()));
+        };
+        Self::new(path, *format, schema)
+    }
+
+    /// Reads all rows of a result file.
+    pub fn read_all(
+        path: impl AsRef<Path>,
+        format: SinkFormat,
+        schema: Schema,
+    ) -> Result<Vec<Row>, ResultError> {
+        std::fs::metadata(path.as_ref())?;
+        Self::new(path, format, schema)?.read_new()
+    }
+
+    /// Returns the rows that were written since the last call. Returns no rows if the file does
+    /// not exist yet.
+    pub fn read_new(&mut self) -> Result<Vec<Row>, ResultError> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        let Some(end) = bytes.iter().rposition(|byte| *byte == b'\n') else {
+            return Ok(Vec::new());
+        };
+        self.offset += end as u64 + 1;
+        let text = String::from_utf8_lossy(&bytes[..end]);
+        let mut rows = Vec::new();
+        for line in text.split('\n') {
+            self.line += 1;
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = match self.format {
+                SinkFormat::Json => self.parse_json(line)?,
+                _ => self.parse_csv(line)?,
+            };
+            rows.extend(row);
+        }
+        Ok(rows)
+    }
+
+    /// Polls the file every `poll_interval` until at least `count` rows were written. Returns
+    /// all rows read while waiting. Fails if `timeout` expires before.
+    pub async fn wait_for_rows(
+        &mut self,
+        count: usize,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<Vec<Row>, ResultError> {
+        let deadline = Instant::now() + timeout;
+        let mut rows = Vec::new();
+        loop {
+            rows.extend(self.read_new()?);
+            if rows.len() >= count {
+                return Ok(rows);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(ResultError::Timeout {
+                    expected: count,
+                    received: rows.len(),
+                });
+            }
+            tokio::time::sleep(poll_interval.min(deadline - now)).await;
+        }
+    }
+
+    /// Parses a CSV line. The first line is skipped if it is a header naming all fields of the
+    /// schema, otherwise the columns are expected in the order of the schema.
+    fn parse_csv(&mut self, line: &str) -> Result<Option<Row>, ResultError> {
+        let values = split_csv(line).map_err(|message| ResultError::InvalidLine {
+            line: self.line,
+            message,
+        })?;
+        if self.columns.is_none() {
+            let header = self.header_columns(&values);
+            let is_header = header.is_some();
+            self.columns = Some(header.unwrap_or_else(|| (0..self.schema.len()).collect()));
+            if is_header {
+                return Ok(None);
+            }
+        }
+        let columns = self.columns.as_ref().expect("columns are set above");
+        let mut row = Row::new();
+        for (field, column) in self.schema.fields().iter().zip(columns) {
+            let Some(value) = values.get(*column) else {
+                return Err(ResultError::InvalidLine {
+                    line: self.line,
+                    message: format!(
+                        "expected {} values but found {}",
+                        self.schema.len(),
+                        values.len()
+                    ),
+                });
+            };
+            row = row.with(field.name(), self.parse_value(field, value)?);
+        }
+        Ok(Some(row))
+    }
+
+    fn header_columns(&self, names: &[String]) -> Option<Vec<usize>> {
+        let names: Vec<&str> = names.iter().map(|name| strip_type(name)).collect();
+        self.schema
+            .fields()
+            .iter()
+            .map(|field| find_field(&names, field.name()))
+            .collect()
+    }
+
+    fn parse_json(&self, line: &str) -> Result<Option<Row>, ResultError> {
+        let object: serde_json::Map<String, Value> =
+            serde_json::from_str(line).map_err(|err| ResultError::InvalidLine {
+                line: self.line,
+                message: err.to_string(),
+            })?;
+        let names: Vec<&str> = object.keys().map(String::as_str).collect();
+        let values: Vec<&Value> = object.values().collect();
+        let mut row = Row::new();
+        for field in self.schema.fields() {
+            let Some(value) = find_field(&names, field.name()).map(|index| values[index]) else {
+                return Err(ResultError::MissingField {
+                    line: self.line,
+                    field: field.name().to_string(),
+                });
+            };
+            let text = match value {
+                Value::String(text) => text.clone(),
+                value => value.to_string(),
+            };
+            row = row.with(field.name(), self.parse_value(field, &text)?);
+        }
+        Ok(Some(row))
+    }
+
+    fn parse_value(&self, field: &Field, text: &str) -> Result<FieldValue, ResultError> {
+        FieldValue::parse(text, field.data_type()).ok_or_else(|| ResultError::InvalidValue {
+            line: self.line,
+            field: field.name().to_string(),
+            value: text.to_string(),
+            data_type: field.data_type(),
+        })
+    }
+}
+
+/// Removes the type the coordinator appends to the names in CSV headers, e.g. `id:INTEGER`.
+fn strip_type(name: &str) -> &str {
+    name.split(':').next().unwrap_or(name).trim()
+}
+
+/// Splits a CSV line into its values. Values may be quoted as described in RFC 4180, i.e. a
+/// quoted value may contain commas and `""` stands for a quote. Line breaks inside quoted values
+/// are not supported since the file is read line by line.
+fn split_csv(line: &str) -> Result<Vec<String>, String> {
+    let mut values = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        value.push('"');
+                    }
+                    Some('"') => break,
+                    Some(char) => value.push(char),
+                    None => return Err("unterminated quoted value".to_string()),
+                }
+            }
+            if !matches!(chars.peek(), None | Some(',')) {
+                return Err("unexpected character after quoted value".to_string());
+            }
+        } else {
+            while let Some(char) = chars.next_if(|char| *char != ',') {
+                value.push(char);
+            }
+        }
+        values.push(value);
+        if chars.next().is_none() {
+            return Ok(values);
+        }
+    }
+}
+
+/// Returns the position of the name that refers to `field`. An exact match is preferred over a
+/// match that ignores the `source$` qualification.
+fn find_field(names: &[&str], field: &str) -> Option<usize> {
+    names.iter().position(|name| *name == field).or_else(|| {
+        names
+            .iter()
+            .position(|name| unqualified(name) == unqualified(field))
+    })
+}
+
+fn unqualified(name: &str) -> &str {
+    name.rsplit('$').next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Write, path::PathBuf, time::Duration};
+
+    use nes_types::{FloatType, IntType, NesType};
+
+    use super::{split_csv, ResultReader};
+    use crate::{
+        query::{
+            schema::Schema,
+            sink::{Sink, SinkFormat},
+        },
+        results::row::{compare_rows, Row},
+    };
+
+    fn schema() -> Schema {
+        Schema::new()
+            .with_field("id", NesType::Int(IntType::Unsigned64))
+            .with_field("value", NesType::Float(FloatType::Bit64))
+            .with_field("valid", NesType::Bool)
+    }
+
+    fn result_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("nes-results-{}-{name}", std::process::id()));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_csv() {
+        let path = result_file(
+            "read.csv",
+            "test$valid:BOOLEAN,test$id:INTEGER(64 bits),test$value:FLOAT(64 bits)\n1,2,0.5\n0,1,1.5\n",
+        );
+        let rows = ResultReader::read_all(&path, SinkFormat::Csv, schema()).unwrap();
+        let expected = [
+            Row::new()
+                .with("id", 1)
+                .with("value", 1.5)
+                .with("valid", false),
+            Row::new()
+                .with("id", 2)
+                .with("value", 0.5)
+                .with("valid", true),
+        ];
+        assert!(compare_rows(&rows, &expected).is_empty());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_split_csv() {
+        assert_eq!(vec!["1", "", "a"], split_csv("1,,a").unwrap());
+        assert_eq!(
+            vec!["a,b", "say \"hi\"", ""],
+            split_csv("\"a,b\",\"say \"\"hi\"\"\",\"\"").unwrap()
+        );
+        assert!(split_csv("\"a,b").is_err());
+        assert!(split_csv("\"a\"b,c").is_err());
+    }
+
+    #[test]
+    fn test_read_json() {
+        let path = result_file(
+            "read.json",
+            "{\"test$id\":1,\"test$value\":1.5,\"test$valid\":true}\n{\"id\":2,\"value\":2,\"valid\":false}\n",
+        );
+        let sink = Sink::json_file(&path, false);
+        let rows = ResultReader::from_sink(&sink, schema())
+            .unwrap()
+            .read_new()
+            .unwrap();
+        let expected = [
+            Row::new()
+                .with("id", 1)
+                .with("value", 1.5)
+                .with("valid", true),
+            Row::new()
+                .with("id", 2)
+                .with("value", 2.0)
+                .with("valid", false),
+        ];
+        assert_eq!(expected.to_vec(), rows);
+        assert!(ResultReader::from_sink(&Sink::Print, schema()).is_err());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tail_csv() {
+        let path = result_file("tail.csv", "1,0.5,");
+        let mut reader = ResultReader::new(&path, SinkFormat::Csv, schema()).unwrap();
+        assert!(reader.read_new().unwrap().is_empty());
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"true\n2,1.5,false\n3,").unwrap();
+        assert_eq!(2, reader.read_new().unwrap().len());
+        file.write_all(b"2.5,true\n").unwrap();
+        let rows = reader
+            .wait_for_rows(1, Duration::from_secs(1), Duration::from_millis(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![Row::new()
+                .with("id", 3)
+                .with("value", 2.5)
+                .with("valid", true)],
+            rows
+        );
+        assert!(reader
+            .wait_for_rows(1, Duration::from_millis(10), Duration::from_millis(1))
+            .await
+            .is_err());
+        fs::remove_file(path).unwrap();
+    }
+}
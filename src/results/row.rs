@@ -0,0 +1,267 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use nes_types::{IntType, NesType};
+
+/// A typed value of a field in a result row.
+#[derive(Debug, Clone)]
+pub enum FieldValue {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Char(char),
+    /// Values of fields with undefined type are kept as they were written.
+    Text(String),
+}
+
+impl FieldValue {
+    /// Parses the textual representation of a value as written by a sink. Booleans may be
+    /// written as `true`/`false` or `1`/`0`.
+    pub fn parse(text: &str, data_type: NesType) -> Option<Self> {
+        let text = text.trim();
+        match data_type {
+            NesType::Bool => match text {
+                "true" | "1" => Some(FieldValue::Bool(true)),
+                "false" | "0" => Some(FieldValue::Bool(false)),
+                _ => None,
+            },
+            NesType::Int(
+                IntType::Unsigned8
+                | IntType::Unsigned16
+                | IntType::Unsigned32
+                | IntType::Unsigned64,
+            ) => text.parse().ok().map(FieldValue::UInt),
+            NesType::Int(_) => text.parse().ok().map(FieldValue::Int),
+            NesType::Float(_) => text.parse().ok().map(FieldValue::Float),
+            NesType::Char => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(char), None) => Some(FieldValue::Char(char)),
+                    _ => None,
+                }
+            }
+            NesType::Undefined => Some(FieldValue::Text(text.to_string())),
+        }
+    }
+}
+
+/// Signed and unsigned integers are equal if they have the same numeric value.
+impl PartialEq for FieldValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (FieldValue::Bool(a), FieldValue::Bool(b)) => a == b,
+            (FieldValue::Int(a), FieldValue::Int(b)) => a == b,
+            (FieldValue::UInt(a), FieldValue::UInt(b)) => a == b,
+            (FieldValue::Int(a), FieldValue::UInt(b))
+            | (FieldValue::UInt(b), FieldValue::Int(a)) => u64::try_from(*a).is_ok_and(|a| a == *b),
+            (FieldValue::Float(a), FieldValue::Float(b)) => a == b,
+            (FieldValue::Char(a), FieldValue::Char(b)) => a == b,
+            (FieldValue::Text(a), FieldValue::Text(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Display for FieldValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldValue::Bool(value) => write!(f, "{value}"),
+            FieldValue::Int(value) => write!(f, "{value}"),
+            FieldValue::UInt(value) => write!(f, "{value}"),
+            FieldValue::Float(value) => write!(f, "{value}"),
+            FieldValue::Char(value) => write!(f, "'{value}'"),
+            FieldValue::Text(value) => write!(f, "\"{value}\""),
+        }
+    }
+}
+
+impl From<bool> for FieldValue {
+    fn from(value: bool) -> Self {
+        FieldValue::Bool(value)
+    }
+}
+
+impl From<i32> for FieldValue {
+    fn from(value: i32) -> Self {
+        FieldValue::Int(value.into())
+    }
+}
+
+impl From<i64> for FieldValue {
+    fn from(value: i64) -> Self {
+        FieldValue::Int(value)
+    }
+}
+
+impl From<u32> for FieldValue {
+    fn from(value: u32) -> Self {
+        FieldValue::UInt(value.into())
+    }
+}
+
+impl From<u64> for FieldValue {
+    fn from(value: u64) -> Self {
+        FieldValue::UInt(value)
+    }
+}
+
+impl From<f64> for FieldValue {
+    fn from(value: f64) -> Self {
+        FieldValue::Float(value)
+    }
+}
+
+impl From<char> for FieldValue {
+    fn from(value: char) -> Self {
+        FieldValue::Char(value)
+    }
+}
+
+impl From<&str> for FieldValue {
+    fn from(value: &str) -> Self {
+        FieldValue::Text(value.to_string())
+    }
+}
+
+/// A result tuple. Values are keyed by the names of the fields of the output schema.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Row {
+    values: BTreeMap<String, FieldValue>,
+}
+
+impl Row {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a value to the row, e.g. `Row::new().with("id", 1).with("valid", true)`.
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<FieldValue>) -> Self {
+        self.values.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&FieldValue> {
+        self.values.get(name)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = (&str, &FieldValue)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl Display for Row {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values: Vec<_> = self
+            .values()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect();
+        write!(f, "{{{}}}", values.join(", "))
+    }
+}
+
+impl<N: Into<String>, V: Into<FieldValue>> FromIterator<(N, V)> for Row {
+    fn from_iter<T: IntoIterator<Item = (N, V)>>(iter: T) -> Self {
+        Self {
+            values: iter
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        }
+    }
+}
+
+/// The difference between the actual and the expected rows of a query.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowDiff {
+    /// Expected rows that were not produced.
+    pub missing: Vec<Row>,
+    /// Produced rows that were not expected.
+    pub unexpected: Vec<Row>,
+}
+
+impl RowDiff {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+impl Display for RowDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} missing rows:", self.missing.len())?;
+        for row in &self.missing {
+            writeln!(f, "  {row}")?;
+        }
+        writeln!(f, "{} unexpected rows:", self.unexpected.len())?;
+        for row in &self.unexpected {
+            writeln!(f, "  {row}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares rows regardless of their order. Duplicates are taken into account, i.e., a row that
+/// is expected twice must be produced twice.
+pub fn compare_rows(actual: &[Row], expected: &[Row]) -> RowDiff {
+    let mut unexpected: Vec<Row> = actual.to_vec();
+    let mut missing = Vec::new();
+    for row in expected {
+        match unexpected.iter().position(|actual| actual == row) {
+            Some(index) => {
+                unexpected.swap_remove(index);
+            }
+            None => missing.push(row.clone()),
+        }
+    }
+    RowDiff {
+        missing,
+        unexpected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nes_types::{FloatType, IntType, NesType};
+
+    use super::{compare_rows, FieldValue, Row};
+
+    #[test]
+    fn test_parse_field_value() {
+        let unsigned = NesType::Int(IntType::Unsigned64);
+        assert_eq!(
+            Some(FieldValue::UInt(42)),
+            FieldValue::parse("42", unsigned)
+        );
+        assert_eq!(Some(FieldValue::Int(42)), FieldValue::parse("42", unsigned));
+        assert_eq!(None, FieldValue::parse("-1", unsigned));
+        assert_eq!(
+            Some(FieldValue::Float(1.5)),
+            FieldValue::parse(" 1.5", NesType::Float(FloatType::Bit64))
+        );
+        assert_eq!(
+            Some(FieldValue::Bool(true)),
+            FieldValue::parse("1", NesType::Bool)
+        );
+        assert_eq!(None, FieldValue::parse("ab", NesType::Char));
+    }
+
+    #[test]
+    fn test_compare_rows() {
+        let row = |id: i32, value: i32| Row::new().with("id", id).with("value", value);
+        let actual = [row(2, 20), row(1, 10), row(1, 10), row(3, 30)];
+        let expected = [row(1, 10), row(2, 20), row(1, 10), row(4, 40)];
+        let diff = compare_rows(&actual, &expected);
+        assert_eq!(vec![row(4, 40)], diff.missing);
+        assert_eq!(vec![row(3, 30)], diff.unexpected);
+        assert!(compare_rows(&actual[..3], &expected[..3]).is_empty());
+    }
+}
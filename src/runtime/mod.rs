@@ -1,2 +1,6 @@
+pub mod nebula_stream_config;
 pub mod nebula_stream_runtime;
+pub mod placement_strategy;
+pub mod query_info;
 pub mod query_state;
+pub mod runtime_error;
@@ -0,0 +1,291 @@
+use std::{net::SocketAddr, time::Duration};
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    StatusCode,
+};
+
+use super::runtime_error::RuntimeError;
+
+/// Decides if and when failed requests are retried.
+///
+/// The policy only applies to GET requests. Requests that change the coordinator's state, e.g.,
+/// submitting or stopping a query, are sent exactly once regardless of the policy. A GET request
+/// is retried if it could not be sent, timed out, or the coordinator responded with 502, 503, or
+/// 504. The backoff doubles with every attempt up to `max_backoff`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+/// Retries a GET request up to three times, starting with a backoff of 100 milliseconds.
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::exponential(3, Duration::from_millis(100))
+    }
+}
+
+impl RetryPolicy {
+    /// Requests are never retried.
+    pub fn none() -> Self {
+        Self::exponential(0, Duration::ZERO)
+    }
+
+    pub fn exponential(max_retries: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Returns the time to wait before the retry following `attempt`, starting at 0.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    pub(crate) fn is_retryable_status(&self, status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    pub(crate) fn is_retryable_error(&self, err: &reqwest::Error) -> bool {
+        err.is_connect() || err.is_timeout()
+    }
+}
+
+/// Configuration of a `NebulaStreamRuntime`. Use `NebulaStreamConfig::builder` to create one.
+#[derive(Debug, Clone)]
+pub struct NebulaStreamConfig {
+    base_url: String,
+    request_timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    default_headers: HeaderMap,
+}
+
+impl Default for NebulaStreamConfig {
+    fn default() -> Self {
+        Self::from_host_port("localhost", 8081)
+    }
+}
+
+impl NebulaStreamConfig {
+    pub fn builder() -> NebulaStreamConfigBuilder {
+        NebulaStreamConfigBuilder::new()
+    }
+
+    pub(crate) fn from_host_port(host: impl Into<String>, port: i32) -> Self {
+        Self {
+            base_url: format!("http://{}:{port}", host.into()),
+            request_timeout: None,
+            connect_timeout: None,
+            retry_policy: RetryPolicy::none(),
+            default_headers: HeaderMap::new(),
+        }
+    }
+
+    /// The URL of the coordinator's REST API without trailing slash, e.g. `http://localhost:8081`.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    pub fn default_headers(&self) -> &HeaderMap {
+        &self.default_headers
+    }
+
+    /// Builds the HTTP client that is shared by all requests of a runtime.
+    pub(crate) fn build_client(&self) -> Result<reqwest::Client, RuntimeError> {
+        let mut builder = reqwest::Client::builder().default_headers(self.default_headers.clone());
+        if let Some(timeout) = self.request_timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Builds a `NebulaStreamConfig`. Defaults to `http://localhost:8081` without timeouts and without
+/// retries, i.e., timeouts and the `RetryPolicy` are opt-in.
+#[derive(Debug, Clone)]
+pub struct NebulaStreamConfigBuilder {
+    url: String,
+    request_timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    default_headers: Vec<(String, String)>,
+}
+
+impl NebulaStreamConfigBuilder {
+    pub fn new() -> Self {
+        let defaults = NebulaStreamConfig::default();
+        Self {
+            url: defaults.base_url,
+            request_timeout: defaults.request_timeout,
+            connect_timeout: defaults.connect_timeout,
+            retry_policy: defaults.retry_policy,
+            default_headers: Vec::new(),
+        }
+    }
+
+    pub fn host_port(mut self, host: impl Into<String>, port: u16) -> Self {
+        self.url = format!("http://{}:{port}", host.into());
+        self
+    }
+
+    pub fn address(mut self, address: SocketAddr) -> Self {
+        self.url = format!("http://{address}");
+        self
+    }
+
+    /// Sets the URL of the coordinator's REST API, e.g. `https://nes.example.org:8081`. The URL
+    /// is validated by `build`.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Sets the timeout of a whole request, from connecting until the response body is read.
+    /// `None` disables the timeout.
+    pub fn request_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout for connecting to the coordinator. `None` disables the timeout.
+    pub fn connect_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Adds a header that is sent with every request, e.g. for authentication.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn build(self) -> Result<NebulaStreamConfig, RuntimeError> {
+        let url = reqwest::Url::parse(&self.url).map_err(|err| {
+            RuntimeError::invalid_config(format!("Invalid coordinator URL {}: {err}", self.url))
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(RuntimeError::invalid_config(format!(
+                "Unsupported scheme {} of coordinator URL, expected http or https",
+                url.scheme()
+            )));
+        }
+        let mut default_headers = HeaderMap::new();
+        for (name, value) in self.default_headers {
+            let header_name = HeaderName::try_from(name.as_str()).map_err(|err| {
+                RuntimeError::invalid_config(format!("Invalid header name {name}: {err}"))
+            })?;
+            let header_value = HeaderValue::try_from(value.as_str()).map_err(|err| {
+                RuntimeError::invalid_config(format!("Invalid value of header {name}: {err}"))
+            })?;
+            default_headers.append(header_name, header_value);
+        }
+        Ok(NebulaStreamConfig {
+            base_url: url.as_str().trim_end_matches('/').to_string(),
+            request_timeout: self.request_timeout,
+            connect_timeout: self.connect_timeout,
+            retry_policy: self.retry_policy,
+            default_headers,
+        })
+    }
+}
+
+impl Default for NebulaStreamConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{NebulaStreamConfig, RetryPolicy};
+
+    #[test]
+    fn test_build_config() {
+        let config = NebulaStreamConfig::builder().build().unwrap();
+        assert_eq!("http://localhost:8081", config.base_url());
+        assert_eq!(None, config.request_timeout());
+        assert_eq!(&RetryPolicy::none(), config.retry_policy());
+        let config = NebulaStreamConfig::builder()
+            .url("https://nes.example.org:8443/")
+            .default_header("Authorization", "Bearer token")
+            .build()
+            .unwrap();
+        assert_eq!("https://nes.example.org:8443", config.base_url());
+        assert_eq!(
+            "Bearer token",
+            config.default_headers()["authorization"].to_str().unwrap()
+        );
+        let config = NebulaStreamConfig::builder()
+            .address(([127, 0, 0, 1], 9000).into())
+            .build()
+            .unwrap();
+        assert_eq!("http://127.0.0.1:9000", config.base_url());
+
+        assert!(NebulaStreamConfig::builder()
+            .url("localhost")
+            .build()
+            .is_err());
+        assert!(NebulaStreamConfig::builder()
+            .url("ftp://localhost:8081")
+            .build()
+            .is_err());
+        assert!(NebulaStreamConfig::builder()
+            .default_header("Invalid Name", "value")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::exponential(5, Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(500));
+        assert_eq!(Duration::from_millis(100), policy.backoff(0));
+        assert_eq!(Duration::from_millis(200), policy.backoff(1));
+        assert_eq!(Duration::from_millis(400), policy.backoff(2));
+        assert_eq!(Duration::from_millis(500), policy.backoff(3));
+        assert_eq!(Duration::from_millis(500), policy.backoff(40));
+        assert_eq!(0, RetryPolicy::none().max_retries());
+    }
+}
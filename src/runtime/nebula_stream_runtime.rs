@@ -1,37 +1,23 @@
-use std::fmt::Display;
+use std::collections::HashMap;
+use std::future::Future;
 
+use nes_types::{FloatType, IntType, NesType};
+use serde_json::Value;
+use tokio::time::Instant;
+
+use crate::query::schema::Schema;
 use crate::query::stringify::stringify_query;
 use crate::query::{Query, QueryBuilder};
-use crate::serialization::protobuf::serialize_query::serialize_request;
+use crate::serialization::protobuf::serialize_query::serialize_request_with_context;
+use crate::serialization::protobuf::serialize_schema::{decode_schema, serialize_named_schema};
 
+use super::nebula_stream_config::NebulaStreamConfig;
+use super::placement_strategy::PlacementStrategy;
+use super::query_info::{
+    json_i64, json_str, ExecutionPlan, OptimizationPhase, QueryInfo, QueryPlanGraph,
+};
 use super::query_state::QueryState;
-
-pub enum PlacementStrategy {
-    BottomUp,
-}
-
-impl ToString for PlacementStrategy {
-    fn to_string(&self) -> String {
-        match self {
-            PlacementStrategy::BottomUp => "BottomUp",
-        }
-        .to_string()
-    }
-}
-
-pub struct RuntimeError(String);
-
-impl Display for RuntimeError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-impl From<reqwest::Error> for RuntimeError {
-    fn from(value: reqwest::Error) -> Self {
-        RuntimeError(format!("{value}"))
-    }
-}
+use super::runtime_error::RuntimeError;
 
 #[derive(Debug)]
 pub struct QueryCatalogEntry {
@@ -40,31 +26,38 @@ pub struct QueryCatalogEntry {
     pub query_string: String,
 }
 
-#[derive(Debug)]
-pub struct NebulaStreamConfig {
-    host: String,
-    port: String,
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogicalSource {
+    pub name: String,
+    pub schema: Schema,
 }
 
+/// Client of the coordinator's REST API. All requests share one connection pool.
 pub struct NebulaStreamRuntime {
     config: NebulaStreamConfig,
+    client: reqwest::Client,
 }
 
 impl NebulaStreamRuntime {
-    // FIXME: This shoudl use make use or rusts IP address type
-    pub fn new(host: impl Into<String>, port: i32) -> Self {
-        let config = NebulaStreamConfig {
-            host: host.into(),
-            port: port.to_string(),
-        };
-        Self { config }
+    /// Creates a runtime that sends requests to `host:port` without timeouts and without retries.
+    /// Use `with_config` to enable them.
+    pub fn new(host: impl Into<String>, port: i32) -> Result<Self, RuntimeError> {
+        Self::with_config(NebulaStreamConfig::from_host_port(host, port))
+    }
+
+    pub fn with_config(config: NebulaStreamConfig) -> Result<Self, RuntimeError> {
+        let client = config.build_client()?;
+        Ok(Self { config, client })
+    }
+
+    pub fn config(&self) -> &NebulaStreamConfig {
+        &self.config
     }
 
     /// This function returns true if runtime is connected and false if not.
     pub async fn check_connection(&self) -> bool {
         log::debug!("Checking connection.");
-        let Ok(response) = reqwest::get(self.coordinator_url("/v1/nes/connectivity/check")).await
-        else {
+        let Ok(response) = self.get("/v1/nes/connectivity/check").await else {
             return false;
         };
         log::trace!("Response status: {}", response.status());
@@ -90,64 +83,53 @@ impl NebulaStreamRuntime {
         query: &Query,
         placement: PlacementStrategy,
     ) -> Result<i64, RuntimeError> {
-        log::debug!("Attempting to Execute Query: {}", stringify_query(query));
-        let client = reqwest::Client::builder().build().unwrap();
-        let request = serialize_request(query, placement);
-        let response = client
+        self.execute_query_with_context(query, placement, &HashMap::new())
+            .await
+    }
+
+    /// Like `execute_query` but adds `context` to the context of the submit request. Entries of
+    /// the placement strategy take precedence over entries in `context`.
+    pub async fn execute_query_with_context(
+        &self,
+        query: &Query,
+        placement: PlacementStrategy,
+        context: &HashMap<String, String>,
+    ) -> Result<i64, RuntimeError> {
+        log::debug!(
+            "Attempting to Execute Query with placement {placement}: {}",
+            stringify_query(query)
+        );
+        let request = serialize_request_with_context(query, placement, context);
+        let response = self
+            .client
             .post(self.coordinator_url("/v1/nes/query/execute-query-ex"))
             .body(request)
             .send()
             .await?;
-
-        log::trace!("Response status: {}", response.status());
-        let body = response.text().await?;
-        log::trace!("Response body: {}", body);
-        let json_value: serde_json::Value = serde_json::from_str(&body).unwrap();
-        let Some(serde_json::Value::Number(number)) = json_value.get("queryId") else {
-            let Some(serde_json::Value::String(message)) = json_value.get("message") else {
-                return Err(RuntimeError(
-                    "The response by the coordinator did not contain a query ID.".into(),
-                ));
-            };
-            return Err(RuntimeError(format!("Error message: {message}.")));
-        };
-        let Some(query_id) = number.as_i64() else {
-            return Err(RuntimeError("Expected query_id to be i64".into()));
-        };
-        Ok(query_id)
+        let body = response_body(response).await?;
+        let json_value: Value = serde_json::from_str(&body)?;
+        if json_value.get("queryId").is_none() {
+            if let Some(message) = json_value.get("message").and_then(Value::as_str) {
+                return Err(RuntimeError::Coordinator {
+                    message: message.to_string(),
+                });
+            }
+        }
+        json_i64(&json_value, "queryId")
     }
 
-    pub async fn registered_queries(&self) -> Result<Vec<QueryCatalogEntry>, reqwest::Error> {
+    pub async fn registered_queries(&self) -> Result<Vec<QueryCatalogEntry>, RuntimeError> {
         log::debug!("Requesting registered queries.");
-        let response =
-            reqwest::get(self.coordinator_url("/v1/nes/queryCatalog/allRegisteredQueries")).await?;
-        log::trace!("Response status: {}", response.status());
-        let body = response.text().await?;
-        log::trace!("Response body: {}", body);
-        let serde_json::Value::Array(json_arr) =
-            serde_json::from_str(&body).expect("Parsing JSON should not fail!")
-        else {
-            panic!("Body should be a JSON Array!")
-        };
+        let response = self
+            .get("/v1/nes/queryCatalog/allRegisteredQueries")
+            .await?;
+        let body = response_body(response).await?;
+        let json_arr: Vec<Value> = serde_json::from_str(&body)?;
         let mut queries = Vec::new();
         for val in json_arr {
-            let Some(serde_json::Value::Number(number)) = val.get("queryId") else {
-                panic!("The response by the coordinator did not contain field queryId")
-            };
-            let Some(query_id) = number.as_i64() else {
-                panic!("Expected query ID to be i64!");
-            };
-            let Some(serde_json::Value::String(status_string)) = val.get("queryStatus") else {
-                panic!("The response by the coordinator did not contain field queryStatus")
-            };
-            let Some(serde_json::Value::String(query_string)) = val.get("queryString") else {
-                panic!("The response by the coordinator did not contain field queryString")
-            };
-
-            let query_status = match status_string.try_into() {
-                Ok(ok) => ok,
-                Err(err) => panic!("{err}"),
-            };
+            let query_id = json_i64(&val, "queryId")?;
+            let query_status = QueryState::try_from(json_str(&val, "queryStatus")?)?;
+            let query_string = json_str(&val, "queryString")?;
             let entry = QueryCatalogEntry {
                 query_id,
                 query_status,
@@ -158,58 +140,424 @@ impl NebulaStreamRuntime {
         Ok(queries)
     }
 
+    /// Returns the catalog entry of a query given the queries id. If the query is not registered
+    /// with the coordinator return None.
+    pub async fn query_info(&self, query_id: i64) -> Result<Option<QueryInfo>, RuntimeError> {
+        log::debug!("Requesting catalog entry of query with id {query_id}.");
+        let response = self
+            .get(&format!("/v1/nes/queryCatalog/status?queryId={query_id}"))
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let body = response_body(response).await?;
+        let json_value: Value = serde_json::from_str(&body)?;
+        Ok(Some(QueryInfo::from_json(&json_value)?))
+    }
+
     /// Returns the status of a query given the queries id. If the query is not registeded with the
     /// coordinator return None.
-    pub async fn query_status(&self, query_id: i64) -> Result<Option<QueryState>, reqwest::Error> {
+    pub async fn query_status(&self, query_id: i64) -> Result<Option<QueryState>, RuntimeError> {
         log::debug!("Extracting status of query with id {query_id}.");
-        let queries = self.registered_queries().await?;
-        let Some(entry) = queries.iter().find(|e| e.query_id == query_id) else {
-            return Ok(None);
-        };
-        Ok(Some(entry.query_status.clone()))
+        let info = self.query_info(query_id).await?;
+        Ok(info.map(|info| info.query_status))
+    }
+
+    /// Returns the logical query plan of a query as a graph of operators.
+    pub async fn query_plan(&self, query_id: i64) -> Result<QueryPlanGraph, RuntimeError> {
+        log::debug!("Requesting query plan of query with id {query_id}.");
+        let json_value = self
+            .get_json(&format!("/v1/nes/query/query-plan?queryId={query_id}"))
+            .await?;
+        QueryPlanGraph::from_json(&json_value)
+    }
+
+    /// Returns the execution plan of a query, i.e., the placement of its sub queries.
+    pub async fn execution_plan(&self, query_id: i64) -> Result<ExecutionPlan, RuntimeError> {
+        log::debug!("Requesting execution plan of query with id {query_id}.");
+        let json_value = self
+            .get_json(&format!("/v1/nes/query/execution-plan?queryId={query_id}"))
+            .await?;
+        ExecutionPlan::from_json(&json_value)
+    }
+
+    /// Returns the query plan of a query after each of the coordinators optimization phases.
+    pub async fn optimization_phases(
+        &self,
+        query_id: i64,
+    ) -> Result<Vec<OptimizationPhase>, RuntimeError> {
+        log::debug!("Requesting optimization phases of query with id {query_id}.");
+        let json_value = self
+            .get_json(&format!(
+                "/v1/nes/query/optimization-phase?queryId={query_id}"
+            ))
+            .await?;
+        OptimizationPhase::from_json(&json_value)
+    }
+
+    /// Polls the status of a query every `poll_interval` until the state fulfills `predicate`.
+    /// Returns the states observed while waiting. Fails if the query reaches a terminal state that
+    /// does not fulfill `predicate` or if `timeout` expires.
+    pub async fn wait_for_state(
+        &self,
+        query_id: i64,
+        predicate: impl Fn(QueryState) -> bool,
+        timeout: std::time::Duration,
+        poll_interval: std::time::Duration,
+    ) -> Result<Vec<QueryState>, RuntimeError> {
+        log::debug!("Waiting for query with id {query_id} to reach the expected state.");
+        poll_state(
+            query_id,
+            || self.query_status(query_id),
+            predicate,
+            timeout,
+            poll_interval,
+        )
+        .await
+    }
+
+    /// Waits until the query with `query_id` is running. See `wait_for_state`.
+    pub async fn wait_for_running(
+        &self,
+        query_id: i64,
+        timeout: std::time::Duration,
+        poll_interval: std::time::Duration,
+    ) -> Result<Vec<QueryState>, RuntimeError> {
+        self.wait_for_state(
+            query_id,
+            |state| state == QueryState::Running,
+            timeout,
+            poll_interval,
+        )
+        .await
     }
 
     /// Returns an error if something went wrong
-    pub async fn stop_query(&self, query_id: i64) -> Result<(), reqwest::Error> {
+    pub async fn stop_query(&self, query_id: i64) -> Result<(), RuntimeError> {
         log::debug!("Stopping query with id {query_id}.");
-        let client = reqwest::Client::builder().build().unwrap();
-        let response = client
+        let response = self
+            .client
             .delete(
                 self.coordinator_url(&(format!("/v1/nes/query/stop-query?queryId={}", query_id))),
             )
             .send()
             .await?;
-        log::trace!("Response status: {}", response.status());
+        response_body(response).await?;
         Ok(())
     }
 
-    pub async fn logical_sources(&self) -> Result<Vec<String>, reqwest::Error> {
+    pub async fn logical_sources(&self) -> Result<Vec<String>, RuntimeError> {
+        Ok(self
+            .request_logical_sources()
+            .await?
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect())
+    }
+
+    /// Returns all logical sources registered with the coordinator together with their schemas.
+    pub async fn logical_sources_with_schema(&self) -> Result<Vec<LogicalSource>, RuntimeError> {
+        self.request_logical_sources()
+            .await?
+            .into_iter()
+            .map(|(name, schema)| {
+                let schema = parse_schema(&schema)
+                    .ok_or_else(|| RuntimeError::missing_field(format!("schema of {name}")))?;
+                Ok(LogicalSource { name, schema })
+            })
+            .collect()
+    }
+
+    /// Requests the names and schema strings of all logical sources.
+    async fn request_logical_sources(&self) -> Result<Vec<(String, String)>, RuntimeError> {
         log::debug!("Requesting logical sources.");
-        let response =
-            reqwest::get(self.coordinator_url("/v1/nes/sourceCatalog/allLogicalSource")).await?;
-        log::trace!("Response status: {}", response.status());
-        let body = response.text().await?;
-        log::trace!("Response body: {}", body);
-        let serde_json::Value::Array(json_arr) =
-            serde_json::from_str(&body).expect("Parsing JSON should not fail!")
-        else {
-            panic!("Body should be a JSON Array!")
-        };
+        let response = self.get("/v1/nes/sourceCatalog/allLogicalSource").await?;
+        let body = response_body(response).await?;
+        let json_arr: Vec<serde_json::Map<String, Value>> = serde_json::from_str(&body)?;
         let mut source_list = Vec::new();
-        for val in json_arr {
-            let serde_json::Value::Object(json_obj) = val else {
-                panic!("Value should be a JSON Object!")
+        for json_obj in json_arr {
+            let Some((source, schema)) = json_obj.into_iter().next() else {
+                return Err(RuntimeError::missing_field("logicalSourceName"));
             };
-            let source = json_obj.keys().next().unwrap();
-            source_list.push(source.clone());
+            let Value::String(schema) = schema else {
+                return Err(RuntimeError::missing_field(format!("schema of {source}")));
+            };
+            source_list.push((source, schema));
         }
         Ok(source_list)
     }
 
-    fn coordinator_url<'a>(&self, end_point: &'a str) -> String {
-        format!(
-            "http://{}:{}{}",
-            self.config.host, self.config.port, end_point
-        )
+    pub async fn logical_source_schema(&self, source_name: &str) -> Result<Schema, RuntimeError> {
+        log::debug!("Requesting schema of logical source {source_name}.");
+        let response = self
+            .get_with_query(
+                "/v1/nes/sourceCatalog/schema",
+                &[("logicalSourceName", source_name)],
+            )
+            .await?;
+        let bytes = response_bytes(response).await?;
+        Ok(decode_schema(&bytes)?)
+    }
+
+    /// Registers a new logical source with the given schema at the coordinator.
+    pub async fn add_logical_source(
+        &self,
+        source_name: &str,
+        schema: &Schema,
+    ) -> Result<(), RuntimeError> {
+        log::debug!("Adding logical source {source_name}.");
+        let response = self
+            .client
+            .post(self.coordinator_url("/v1/nes/sourceCatalog/addLogicalSource-ex"))
+            .body(serialize_named_schema(source_name, schema))
+            .send()
+            .await?;
+        response_body(response).await?;
+        Ok(())
+    }
+
+    /// Replaces the schema of an existing logical source.
+    pub async fn update_logical_source(
+        &self,
+        source_name: &str,
+        schema: &Schema,
+    ) -> Result<(), RuntimeError> {
+        log::debug!("Updating logical source {source_name}.");
+        let response = self
+            .client
+            .post(self.coordinator_url("/v1/nes/sourceCatalog/updateLogicalSource-ex"))
+            .body(serialize_named_schema(source_name, schema))
+            .send()
+            .await?;
+        response_body(response).await?;
+        Ok(())
+    }
+
+    pub async fn delete_logical_source(&self, source_name: &str) -> Result<(), RuntimeError> {
+        log::debug!("Deleting logical source {source_name}.");
+        let response = self
+            .client
+            .delete(self.coordinator_url("/v1/nes/sourceCatalog/deleteLogicalSource"))
+            .query(&[("logicalSourceName", source_name)])
+            .send()
+            .await?;
+        response_body(response).await?;
+        Ok(())
+    }
+
+    async fn get_json(&self, end_point: &str) -> Result<Value, RuntimeError> {
+        let response = self.get(end_point).await?;
+        let body = response_body(response).await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    async fn get(&self, end_point: &str) -> Result<reqwest::Response, RuntimeError> {
+        self.get_with_query(end_point, &[]).await
+    }
+
+    /// Sends a GET request and retries it according to the configured `RetryPolicy`. The query
+    /// parameters are URL encoded.
+    async fn get_with_query(
+        &self,
+        end_point: &str,
+        query: &[(&str, &str)],
+    ) -> Result<reqwest::Response, RuntimeError> {
+        let policy = self.config.retry_policy();
+        let url = self.coordinator_url(end_point);
+        let mut attempt = 0;
+        loop {
+            let result = self.client.get(&url).query(query).send().await;
+            let retryable = match &result {
+                Ok(response) => policy.is_retryable_status(response.status()),
+                Err(err) => policy.is_retryable_error(err),
+            };
+            if !retryable || attempt >= policy.max_retries() {
+                return Ok(result?);
+            }
+            let backoff = policy.backoff(attempt);
+            log::debug!("Request to {url} failed, retrying in {backoff:?}.");
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    fn coordinator_url(&self, end_point: &str) -> String {
+        format!("{}{}", self.config.base_url(), end_point)
+    }
+}
+
+/// Reads the body of a coordinator response. Returns an error if the coordinator did not respond
+/// with a success status.
+async fn response_bytes(response: reqwest::Response) -> Result<Vec<u8>, RuntimeError> {
+    let status = response.status();
+    log::trace!("Response status: {}", status);
+    let bytes = response.bytes().await?.to_vec();
+    if status.is_success() {
+        return Ok(bytes);
+    }
+    let body = String::from_utf8_lossy(&bytes).into_owned();
+    let message = serde_json::from_str::<Value>(&body).ok().and_then(|json| {
+        json.get("message")
+            .and_then(Value::as_str)
+            .map(String::from)
+    });
+    match message {
+        Some(message) => Err(RuntimeError::Coordinator { message }),
+        None => Err(RuntimeError::HttpStatus { status, body }),
+    }
+}
+
+/// Like `response_bytes` but returns the body as text.
+async fn response_body(response: reqwest::Response) -> Result<String, RuntimeError> {
+    let bytes = response_bytes(response).await?;
+    let body = String::from_utf8_lossy(&bytes).into_owned();
+    log::trace!("Response body: {}", body);
+    Ok(body)
+}
+
+/// Formats a schema the way the coordinator lists it, e.g. `id:UINT64 value:FLOAT64`.
+pub(crate) fn format_schema(schema: &Schema) -> String {
+    let fields: Vec<_> = schema
+        .fields()
+        .iter()
+        .map(|field| format!("{}:{}", field.name(), type_name(field.data_type())))
+        .collect();
+    fields.join(" ")
+}
+
+/// Parses a schema as listed by the coordinator. Returns `None` for malformed fields or unknown
+/// type names.
+pub(crate) fn parse_schema(schema: &str) -> Option<Schema> {
+    schema
+        .split_whitespace()
+        .try_fold(Schema::new(), |schema, field| {
+            let (name, data_type) = field.rsplit_once(':')?;
+            Some(schema.with_field(name, parse_type_name(data_type)?))
+        })
+}
+
+fn type_name(data_type: NesType) -> &'static str {
+    match data_type {
+        NesType::Undefined => "UNDEFINED",
+        NesType::Char => "CHAR",
+        NesType::Bool => "BOOLEAN",
+        NesType::Int(IntType::Signed8) => "INT8",
+        NesType::Int(IntType::Signed16) => "INT16",
+        NesType::Int(IntType::Signed32) => "INT32",
+        NesType::Int(IntType::Signed64) => "INT64",
+        NesType::Int(IntType::Unsigned8) => "UINT8",
+        NesType::Int(IntType::Unsigned16) => "UINT16",
+        NesType::Int(IntType::Unsigned32) => "UINT32",
+        NesType::Int(IntType::Unsigned64) => "UINT64",
+        NesType::Float(FloatType::Bit32) => "FLOAT32",
+        NesType::Float(FloatType::Bit64) => "FLOAT64",
+    }
+}
+
+fn parse_type_name(name: &str) -> Option<NesType> {
+    let data_type = match name {
+        "UNDEFINED" => NesType::Undefined,
+        "CHAR" => NesType::Char,
+        "BOOLEAN" => NesType::Bool,
+        "INT8" => NesType::Int(IntType::Signed8),
+        "INT16" => NesType::Int(IntType::Signed16),
+        "INT32" => NesType::Int(IntType::Signed32),
+        "INT64" => NesType::Int(IntType::Signed64),
+        "UINT8" => NesType::Int(IntType::Unsigned8),
+        "UINT16" => NesType::Int(IntType::Unsigned16),
+        "UINT32" => NesType::Int(IntType::Unsigned32),
+        "UINT64" => NesType::Int(IntType::Unsigned64),
+        "FLOAT32" => NesType::Float(FloatType::Bit32),
+        "FLOAT64" => NesType::Float(FloatType::Bit64),
+        _ => return None,
+    };
+    Some(data_type)
+}
+
+/// Calls `status` every `poll_interval` until the state fulfills `predicate`, see
+/// `NebulaStreamRuntime::wait_for_state`.
+async fn poll_state<F, Fut>(
+    query_id: i64,
+    mut status: F,
+    predicate: impl Fn(QueryState) -> bool,
+    timeout: std::time::Duration,
+    poll_interval: std::time::Duration,
+) -> Result<Vec<QueryState>, RuntimeError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<QueryState>, RuntimeError>>,
+{
+    let deadline = Instant::now() + timeout;
+    let mut history = Vec::new();
+    loop {
+        if let Some(state) = status().await? {
+            if history.last() != Some(&state) {
+                log::trace!("Query with id {query_id} transitioned to state {state}.");
+                history.push(state);
+            }
+            if predicate(state) {
+                return Ok(history);
+            }
+            if state.is_terminal() {
+                return Err(RuntimeError::UnexpectedQueryState { query_id, history });
+            }
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(RuntimeError::Timeout { query_id, history });
+        }
+        tokio::time::sleep(poll_interval.min(deadline - now)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{poll_state, QueryState, RuntimeError};
+
+    /// Polls a query that goes through `states` and then stays in the last state.
+    async fn poll(
+        states: &[Option<QueryState>],
+        timeout: Duration,
+    ) -> Result<Vec<QueryState>, RuntimeError> {
+        let mut states = states.iter().copied();
+        let mut last = None;
+        let status = || {
+            last = states.next().unwrap_or(last);
+            std::future::ready(Ok(last))
+        };
+        let is_running = |state| state == QueryState::Running;
+        poll_state(1, status, is_running, timeout, Duration::from_millis(1)).await
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_state() {
+        use QueryState::*;
+        let states = [
+            None,
+            Some(Registered),
+            Some(Registered),
+            Some(Optimizing),
+            Some(Running),
+        ];
+        assert_eq!(
+            vec![Registered, Optimizing, Running],
+            poll(&states, Duration::from_secs(1)).await.unwrap()
+        );
+
+        let states = [Some(Registered), Some(Failed)];
+        let result = poll(&states, Duration::from_secs(1)).await;
+        assert!(matches!(
+            result,
+            Err(RuntimeError::UnexpectedQueryState { history, .. }) if history == [Registered, Failed]
+        ));
+
+        let states = [Some(Registered)];
+        let result = poll(&states, Duration::from_millis(10)).await;
+        assert!(matches!(
+            result,
+            Err(RuntimeError::Timeout { history, .. }) if history == [Registered]
+        ));
     }
 }
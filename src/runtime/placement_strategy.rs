@@ -0,0 +1,124 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// The strategy the coordinator uses to place the operators of a query on the worker topology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementStrategy {
+    TopDown,
+    BottomUp,
+    /// Iterative fault-tolerant and cost-optimized placement.
+    Ifcop,
+    /// Placement by solving an integer linear program.
+    Ilp,
+    MlHeuristic,
+    /// Placement computed by an external ELEGANT planner for the given objective.
+    Elegant(ElegantObjective),
+    /// Placement given by the user through the coordinator's topology annotations.
+    Manual,
+}
+
+/// The objective an ELEGANT placement optimizes for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElegantObjective {
+    Performance,
+    Energy,
+    Balanced,
+}
+
+impl PlacementStrategy {
+    /// Returns the name the coordinator uses for this strategy, e.g. `BottomUp`.
+    pub const fn as_coordinator_str(&self) -> &'static str {
+        match self {
+            PlacementStrategy::TopDown => "TopDown",
+            PlacementStrategy::BottomUp => "BottomUp",
+            PlacementStrategy::Ifcop => "IFCOP",
+            PlacementStrategy::Ilp => "ILP",
+            PlacementStrategy::MlHeuristic => "MlHeuristic",
+            PlacementStrategy::Elegant(ElegantObjective::Performance) => "ELEGANT_PERFORMANCE",
+            PlacementStrategy::Elegant(ElegantObjective::Energy) => "ELEGANT_ENERGY",
+            PlacementStrategy::Elegant(ElegantObjective::Balanced) => "ELEGANT_BALANCED",
+            PlacementStrategy::Manual => "Manual",
+        }
+    }
+
+    /// Returns the entries this strategy adds to the context of a submit request: the name of the
+    /// strategy under `placement`.
+    pub fn context_entries(&self) -> Vec<(String, String)> {
+        vec![(
+            "placement".to_string(),
+            self.as_coordinator_str().to_string(),
+        )]
+    }
+}
+
+impl Display for PlacementStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_coordinator_str())
+    }
+}
+
+/// Parses the coordinator name of a strategy.
+impl FromStr for PlacementStrategy {
+    type Err = PlacementStrategyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TopDown" => Ok(Self::TopDown),
+            "BottomUp" => Ok(Self::BottomUp),
+            "IFCOP" => Ok(Self::Ifcop),
+            "ILP" => Ok(Self::Ilp),
+            "MlHeuristic" => Ok(Self::MlHeuristic),
+            "ELEGANT_PERFORMANCE" => Ok(Self::Elegant(ElegantObjective::Performance)),
+            "ELEGANT_ENERGY" => Ok(Self::Elegant(ElegantObjective::Energy)),
+            "ELEGANT_BALANCED" => Ok(Self::Elegant(ElegantObjective::Balanced)),
+            "Manual" => Ok(Self::Manual),
+            _ => Err(PlacementStrategyParseError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PlacementStrategyParseError(String);
+
+impl Display for PlacementStrategyParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unable to parse {} as Placement Strategy. Unknown Strategy!",
+            self.0
+        )
+    }
+}
+
+impl Error for PlacementStrategyParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ElegantObjective, PlacementStrategy};
+
+    #[test]
+    fn test_parse_placement_strategy() {
+        let strategies = [
+            PlacementStrategy::TopDown,
+            PlacementStrategy::BottomUp,
+            PlacementStrategy::Ifcop,
+            PlacementStrategy::Ilp,
+            PlacementStrategy::MlHeuristic,
+            PlacementStrategy::Elegant(ElegantObjective::Performance),
+            PlacementStrategy::Elegant(ElegantObjective::Energy),
+            PlacementStrategy::Elegant(ElegantObjective::Balanced),
+            PlacementStrategy::Manual,
+        ];
+        for strategy in strategies {
+            assert_eq!(strategy, strategy.to_string().parse().unwrap());
+        }
+        assert!("Random".parse::<PlacementStrategy>().is_err());
+    }
+
+    #[test]
+    fn test_context_entries() {
+        assert_eq!(
+            vec![("placement".to_string(), "ILP".to_string())],
+            PlacementStrategy::Ilp.context_entries()
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use serde_json::Value;
+
+use super::{query_state::QueryState, runtime_error::RuntimeError};
+
+/// The catalog entry of a single query as returned by the coordinator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryInfo {
+    pub query_id: i64,
+    pub query_status: QueryState,
+    pub query_string: String,
+    pub query_plan: Option<String>,
+    pub meta_data: Option<String>,
+}
+
+/// The logical plan of a query as a graph of operators.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QueryPlanGraph {
+    pub nodes: Vec<QueryPlanNode>,
+    pub edges: Vec<QueryPlanEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlanNode {
+    pub id: i64,
+    pub title: String,
+    pub node_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlanEdge {
+    pub source: i64,
+    pub target: i64,
+}
+
+/// The placement of a query on the worker topology.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExecutionPlan {
+    pub execution_nodes: Vec<ExecutionNode>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionNode {
+    pub execution_node_id: i64,
+    pub topology_node_id: Option<i64>,
+    pub ip_address: Option<String>,
+    pub sub_queries: Vec<ScheduledSubQuery>,
+}
+
+/// A part of a query that is deployed on a single execution node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledSubQuery {
+    pub sub_query_id: i64,
+    pub sub_query_plan: String,
+}
+
+/// The query plan after one of the coordinators optimization phases.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptimizationPhase {
+    pub name: String,
+    pub query_plan: String,
+}
+
+impl QueryInfo {
+    pub(crate) fn from_json(value: &Value) -> Result<Self, RuntimeError> {
+        Ok(QueryInfo {
+            query_id: json_i64(value, "queryId")?,
+            query_status: QueryState::try_from(json_str(value, "queryStatus")?)?,
+            query_string: json_str(value, "queryString")?.to_string(),
+            query_plan: json_opt_str(value, "queryPlan"),
+            meta_data: json_opt_str(value, "queryMetaData"),
+        })
+    }
+}
+
+impl QueryPlanGraph {
+    pub(crate) fn from_json(value: &Value) -> Result<Self, RuntimeError> {
+        let nodes = json_array(value, "nodes")?
+            .iter()
+            .map(|node| {
+                Ok(QueryPlanNode {
+                    id: json_i64(node, "id")?,
+                    title: json_str(node, "title")?.to_string(),
+                    node_type: json_str(node, "nodeType")?.to_string(),
+                })
+            })
+            .collect::<Result<_, RuntimeError>>()?;
+        let edges = json_array(value, "edges")?
+            .iter()
+            .map(|edge| {
+                Ok(QueryPlanEdge {
+                    source: json_i64(edge, "source")?,
+                    target: json_i64(edge, "target")?,
+                })
+            })
+            .collect::<Result<_, RuntimeError>>()?;
+        Ok(QueryPlanGraph { nodes, edges })
+    }
+}
+
+impl ExecutionPlan {
+    pub(crate) fn from_json(value: &Value) -> Result<Self, RuntimeError> {
+        let execution_nodes = json_array(value, "executionNodes")?
+            .iter()
+            .map(ExecutionNode::from_json)
+            .collect::<Result<_, RuntimeError>>()?;
+        Ok(ExecutionPlan { execution_nodes })
+    }
+}
+
+impl ExecutionNode {
+    fn from_json(value: &Value) -> Result<Self, RuntimeError> {
+        let sub_queries = match value.get("ScheduledDecomposedQueries") {
+            Some(Value::Array(queries)) => queries
+                .iter()
+                .map(|query| {
+                    Ok(ScheduledSubQuery {
+                        sub_query_id: json_i64(query, "decomposedQueryId")?,
+                        sub_query_plan: json_str(query, "decomposedQueryPlan")?.to_string(),
+                    })
+                })
+                .collect::<Result<_, RuntimeError>>()?,
+            _ => Vec::new(),
+        };
+        Ok(ExecutionNode {
+            execution_node_id: json_i64(value, "executionNodeId")?,
+            topology_node_id: value.get("topologyNodeId").and_then(Value::as_i64),
+            ip_address: json_opt_str(value, "topologyNodeIpAddress"),
+            sub_queries,
+        })
+    }
+}
+
+impl OptimizationPhase {
+    pub(crate) fn from_json(value: &Value) -> Result<Vec<Self>, RuntimeError> {
+        let Value::Object(phases) = value else {
+            return Err(RuntimeError::missing_field("optimizationPhases"));
+        };
+        phases
+            .iter()
+            .map(|(name, plan)| {
+                let Some(query_plan) = plan.as_str() else {
+                    return Err(RuntimeError::missing_field(name));
+                };
+                Ok(OptimizationPhase {
+                    name: name.clone(),
+                    query_plan: query_plan.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn json_i64(value: &Value, field: &str) -> Result<i64, RuntimeError> {
+    value
+        .get(field)
+        .and_then(Value::as_i64)
+        .ok_or_else(|| RuntimeError::missing_field(field))
+}
+
+pub(crate) fn json_str<'a>(value: &'a Value, field: &str) -> Result<&'a str, RuntimeError> {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .ok_or_else(|| RuntimeError::missing_field(field))
+}
+
+fn json_opt_str(value: &Value, field: &str) -> Option<String> {
+    value.get(field).and_then(Value::as_str).map(String::from)
+}
+
+fn json_array<'a>(value: &'a Value, field: &str) -> Result<&'a Vec<Value>, RuntimeError> {
+    value
+        .get(field)
+        .and_then(Value::as_array)
+        .ok_or_else(|| RuntimeError::missing_field(field))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_query_info_from_json() {
+        let value = json!({
+            "queryId": 3,
+            "queryStatus": "RUNNING",
+            "queryString": "Query::from(\"test\").sink(PrintSinkDescriptor::create());",
+            "queryPlan": "SINK(opId: 2)\n  SOURCE(opId: 1)",
+        });
+        let info = QueryInfo::from_json(&value).unwrap();
+        assert_eq!(3, info.query_id);
+        assert_eq!(QueryState::Running, info.query_status);
+        assert!(info.query_plan.is_some());
+        assert!(info.meta_data.is_none());
+        assert!(QueryInfo::from_json(&json!({"queryId": 3})).is_err());
+    }
+
+    #[test]
+    fn test_query_plan_from_json() {
+        let value = json!({
+            "nodes": [
+                {"id": 1, "title": "SOURCE(opId: 1)", "nodeType": "Source"},
+                {"id": 2, "title": "SINK(opId: 2)", "nodeType": "Sink"},
+            ],
+            "edges": [{"source": 1, "target": 2}],
+        });
+        let plan = QueryPlanGraph::from_json(&value).unwrap();
+        assert_eq!(2, plan.nodes.len());
+        assert_eq!(
+            vec![QueryPlanEdge {
+                source: 1,
+                target: 2
+            }],
+            plan.edges
+        );
+    }
+
+    #[test]
+    fn test_execution_plan_from_json() {
+        let value = json!({
+            "executionNodes": [{
+                "executionNodeId": 1,
+                "topologyNodeId": 1,
+                "topologyNodeIpAddress": "127.0.0.1",
+                "ScheduledDecomposedQueries": [
+                    {"decomposedQueryId": 4, "decomposedQueryPlan": "SINK(opId: 2)"},
+                ],
+            }],
+        });
+        let plan = ExecutionPlan::from_json(&value).unwrap();
+        assert_eq!(1, plan.execution_nodes.len());
+        assert_eq!(4, plan.execution_nodes[0].sub_queries[0].sub_query_id);
+    }
+
+    #[test]
+    fn test_optimization_phases_keep_order() {
+        let value: Value = serde_json::from_str(
+            r#"{
+                "OriginalQueryPlan": "SINK(opId: 2)",
+                "QueryPlanAfterRewrite": "SINK(opId: 2)",
+                "ExecutedQueryPlan": "SINK(opId: 2)"
+            }"#,
+        )
+        .unwrap();
+        let names: Vec<_> = OptimizationPhase::from_json(&value)
+            .unwrap()
+            .into_iter()
+            .map(|phase| phase.name)
+            .collect();
+        assert_eq!(
+            vec![
+                "OriginalQueryPlan",
+                "QueryPlanAfterRewrite",
+                "ExecutedQueryPlan"
+            ],
+            names
+        );
+    }
+}
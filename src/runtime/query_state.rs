@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{error::Error, fmt::Display};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum QueryState {
@@ -23,6 +23,38 @@ pub enum QueryState {
     Explained,
 }
 
+impl QueryState {
+    /// Returns true if the query can not change its state anymore.
+    pub const fn is_terminal(&self) -> bool {
+        matches!(self, QueryState::Stopped | QueryState::Failed)
+    }
+
+    /// Returns the name the coordinator uses for this state, e.g. `RUNNING`.
+    pub const fn as_coordinator_str(&self) -> &'static str {
+        match self {
+            QueryState::Registered => "REGISTERED",
+            QueryState::Optimizing => "OPTIMIZING",
+            QueryState::MarkedForDeployment => "MARKED_FOR_DEPLOYMENT",
+            QueryState::MarkedForRedeployment => "MARKED_FOR_REDEPLOYMENT",
+            QueryState::MarkedForMigration => "MARKED_FOR_MIGRATION",
+            QueryState::Deployed => "DEPLOYED",
+            QueryState::Redeployed => "REDEPLOYED",
+            QueryState::Running => "RUNNING",
+            QueryState::Migrating => "MIGRATING",
+            QueryState::MarkedForHardStop => "MARKED_FOR_HARD_STOP",
+            QueryState::MarkedForSoftStop => "MARKED_FOR_SOFT_STOP",
+            QueryState::SoftStopTriggered => "SOFT_STOP_TRIGGERED",
+            QueryState::SoftStopCompleted => "SOFT_STOP_COMPLETED",
+            QueryState::Stopped => "STOPPED",
+            QueryState::MarkedForFailure => "MARKED_FOR_FAILURE",
+            QueryState::Failed => "FAILED",
+            QueryState::Restarting => "RESTARTING",
+            QueryState::MigrationCompleted => "MIGRATION_COMPLETED",
+            QueryState::Explained => "EXPLAINED",
+        }
+    }
+}
+
 impl TryFrom<&String> for QueryState {
     type Error = QueryStateParseError;
 
@@ -107,3 +139,5 @@ impl Display for QueryStateParseError {
         )
     }
 }
+
+impl Error for QueryStateParseError {}
@@ -0,0 +1,117 @@
+use std::{error::Error, fmt::Display};
+
+use reqwest::StatusCode;
+
+use super::query_state::{QueryState, QueryStateParseError};
+use crate::serialization::protobuf::deserialize_error::DeserializeError;
+
+/// Errors returned by the `NebulaStreamRuntime` when communicating with the coordinator.
+#[derive(Debug)]
+pub enum RuntimeError {
+    /// The request could not be sent or the response could not be received.
+    Transport(reqwest::Error),
+    /// The coordinator responded with a non-success status code.
+    HttpStatus { status: StatusCode, body: String },
+    /// The response body is not valid JSON.
+    JsonDecode(serde_json::Error),
+    /// The response body is not a valid protobuf message.
+    ProtobufDecode(DeserializeError),
+    /// The response is missing a field or the field has an unexpected type.
+    MissingField { field: String },
+    /// The coordinator responded with an unknown query state.
+    InvalidQueryState(QueryStateParseError),
+    /// The coordinator reported an error message.
+    Coordinator { message: String },
+    /// The query did not reach the expected state in time.
+    Timeout {
+        query_id: i64,
+        history: Vec<QueryState>,
+    },
+    /// The query reached a terminal state other than the expected one.
+    UnexpectedQueryState {
+        query_id: i64,
+        history: Vec<QueryState>,
+    },
+    /// The runtime configuration is invalid, e.g., the coordinator URL cannot be parsed.
+    InvalidConfig { message: String },
+}
+
+impl RuntimeError {
+    pub(crate) fn missing_field(field: impl Into<String>) -> Self {
+        RuntimeError::MissingField {
+            field: field.into(),
+        }
+    }
+
+    pub(crate) fn invalid_config(message: impl Into<String>) -> Self {
+        RuntimeError::InvalidConfig {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuntimeError::Transport(err) => write!(f, "Transport error: {err}"),
+            RuntimeError::HttpStatus { status, body } => {
+                write!(f, "Coordinator responded with status {status}: {body}")
+            }
+            RuntimeError::JsonDecode(err) => write!(f, "Unable to decode JSON response: {err}"),
+            RuntimeError::ProtobufDecode(err) => write!(f, "{err}"),
+            RuntimeError::MissingField { field } => write!(
+                f,
+                "The response by the coordinator did not contain a valid field {field}."
+            ),
+            RuntimeError::InvalidQueryState(err) => write!(f, "{err}"),
+            RuntimeError::Coordinator { message } => write!(f, "Error message: {message}."),
+            RuntimeError::Timeout { query_id, history } => write!(
+                f,
+                "Query with id {query_id} did not reach the expected state in time. Observed states: {history:?}."
+            ),
+            RuntimeError::UnexpectedQueryState { query_id, history } => write!(
+                f,
+                "Query with id {query_id} terminated before reaching the expected state. Observed states: {history:?}."
+            ),
+            RuntimeError::InvalidConfig { message } => {
+                write!(f, "Invalid runtime configuration: {message}.")
+            }
+        }
+    }
+}
+
+impl Error for RuntimeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RuntimeError::Transport(err) => Some(err),
+            RuntimeError::JsonDecode(err) => Some(err),
+            RuntimeError::ProtobufDecode(err) => Some(err),
+            RuntimeError::InvalidQueryState(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for RuntimeError {
+    fn from(value: reqwest::Error) -> Self {
+        RuntimeError::Transport(value)
+    }
+}
+
+impl From<serde_json::Error> for RuntimeError {
+    fn from(value: serde_json::Error) -> Self {
+        RuntimeError::JsonDecode(value)
+    }
+}
+
+impl From<DeserializeError> for RuntimeError {
+    fn from(value: DeserializeError) -> Self {
+        RuntimeError::ProtobufDecode(value)
+    }
+}
+
+impl From<QueryStateParseError> for RuntimeError {
+    fn from(value: QueryStateParseError) -> Self {
+        RuntimeError::InvalidQueryState(value)
+    }
+}
@@ -0,0 +1,42 @@
+use std::{error::Error, fmt::Display};
+
+/// Errors that occur when decoding protobuf messages sent by the coordinator.
+#[derive(Debug)]
+pub enum DeserializeError {
+    /// The bytes are not a valid protobuf message.
+    Decode(prost::DecodeError),
+    /// A required field of a message is not set.
+    MissingField(String),
+    /// The message is valid but can not be represented by the client.
+    Unsupported(String),
+    /// The operators of a query plan do not form a tree, e.g. because of a cycle.
+    InvalidPlan(String),
+}
+
+impl Display for DeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeserializeError::Decode(err) => write!(f, "Unable to decode protobuf message: {err}"),
+            DeserializeError::MissingField(field) => {
+                write!(f, "Protobuf message is missing field {field}.")
+            }
+            DeserializeError::Unsupported(message) => write!(f, "Unsupported message: {message}."),
+            DeserializeError::InvalidPlan(message) => write!(f, "Invalid query plan: {message}."),
+        }
+    }
+}
+
+impl Error for DeserializeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DeserializeError::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<prost::DecodeError> for DeserializeError {
+    fn from(value: prost::DecodeError) -> Self {
+        DeserializeError::Decode(value)
+    }
+}
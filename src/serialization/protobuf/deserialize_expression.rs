@@ -0,0 +1,177 @@
+use nes_types::NesType;
+use prost::Name;
+use prost_types::Any;
+
+use super::{
+    deserialize_error::DeserializeError,
+    nes::{
+        serializable_data_value::BasicValue,
+        serializable_expression::{
+            AbsExpression, AddExpression, AndExpression, CaseExpression, CeilExpression,
+            ConstantValueExpression, DivExpression, EqualsExpression, ExpExpression,
+            FieldAccessExpression, FieldRenameExpression, FloorExpression, GreaterEqualsExpression,
+            GreaterExpression, LessEqualsExpression, LessExpression, ModExpression, MulExpression,
+            NegateExpression, OrExpression, PowExpression, RoundExpression, SqrtExpression,
+            SubExpression, WhenExpression,
+        },
+        SerializableDataType, SerializableExpression,
+    },
+    serialize_data_type::deserialize_data_type,
+};
+use crate::expression::{
+    binary_expression::BinaryOp,
+    case_expression::{CaseExpr, WhenBranch},
+    expression::RawExpr,
+    field::Field,
+    literal::Literal,
+    unary_expression::UnaryOp,
+};
+
+pub fn type_url_is<M: Name>(any: &Any) -> bool {
+    any.type_url == M::type_url()
+}
+
+pub fn required<T>(value: Option<T>, name: &str) -> Result<T, DeserializeError> {
+    value.ok_or_else(|| DeserializeError::MissingField(name.to_string()))
+}
+
+fn optional_data_type(
+    data_type: &Option<SerializableDataType>,
+) -> Result<NesType, DeserializeError> {
+    match data_type {
+        Some(data_type) => deserialize_data_type(data_type),
+        None => Ok(NesType::Undefined),
+    }
+}
+
+fn deserialize_child(expr: Option<SerializableExpression>) -> Result<RawExpr, DeserializeError> {
+    deserialize_expression(&required(expr, "child of expression")?)
+}
+
+macro_rules! unary_expr {
+    ($details:expr, $data_type:expr, $expr:ident, $operator:expr) => {
+        if type_url_is::<$expr>($details) {
+            let $expr { child } = $details.to_msg()?;
+            return Ok(RawExpr::unary(
+                deserialize_child(child)?,
+                $operator,
+                $data_type,
+            ));
+        }
+    };
+}
+
+macro_rules! binary_expr {
+    ($details:expr, $data_type:expr, $expr:ident, $operator:expr) => {
+        if type_url_is::<$expr>($details) {
+            let $expr { left, right } = $details.to_msg()?;
+            let lhs = deserialize_child(left)?;
+            let rhs = deserialize_child(right)?;
+            return Ok(RawExpr::binary(rhs, lhs, $operator, $data_type));
+        }
+    };
+}
+
+pub fn deserialize_expression(expr: &SerializableExpression) -> Result<RawExpr, DeserializeError> {
+    let data_type = optional_data_type(&expr.stamp)?;
+    let details = required(expr.details.as_ref(), "details of expression")?;
+    if type_url_is::<ConstantValueExpression>(details) {
+        let constant: ConstantValueExpression = details.to_msg()?;
+        let value = required(constant.value, "value of constant")?;
+        let value: BasicValue = required(value.value, "value of constant")?.to_msg()?;
+        let data_type = optional_data_type(&value.r#type)?;
+        return Ok(RawExpr::Literal(Literal::typed(value.value, data_type)));
+    }
+    if type_url_is::<FieldAccessExpression>(details)
+        || type_url_is::<FieldRenameExpression>(details)
+    {
+        return Ok(RawExpr::Field(deserialize_field(expr)?));
+    }
+    if type_url_is::<CaseExpression>(details) {
+        let case: CaseExpression = details.to_msg()?;
+        let mut branches = Vec::new();
+        for when in case.left {
+            let when_details = required(when.details.as_ref(), "details of when expression")?;
+            let WhenExpression { left, right } = when_details.to_msg()?;
+            branches.push(WhenBranch {
+                condition: deserialize_child(left)?,
+                value: deserialize_child(right)?,
+            });
+        }
+        return Ok(RawExpr::Case(CaseExpr {
+            branches,
+            default: Box::new(deserialize_child(case.right)?),
+            data_type,
+        }));
+    }
+
+    // Unary
+    unary_expr!(details, data_type, NegateExpression, UnaryOp::Negate);
+    unary_expr!(details, data_type, AbsExpression, UnaryOp::Absolute);
+    unary_expr!(details, data_type, CeilExpression, UnaryOp::Ceil);
+    unary_expr!(details, data_type, FloorExpression, UnaryOp::Floor);
+    unary_expr!(details, data_type, RoundExpression, UnaryOp::Round);
+    unary_expr!(details, data_type, SqrtExpression, UnaryOp::Sqrt);
+    unary_expr!(details, data_type, ExpExpression, UnaryOp::Exp);
+
+    // Logical
+    binary_expr!(details, data_type, AndExpression, BinaryOp::And);
+    binary_expr!(details, data_type, OrExpression, BinaryOp::Or);
+
+    // Relational
+    binary_expr!(details, data_type, EqualsExpression, BinaryOp::Equals);
+    binary_expr!(details, data_type, GreaterExpression, BinaryOp::Greater);
+    binary_expr!(
+        details,
+        data_type,
+        GreaterEqualsExpression,
+        BinaryOp::GreaterEquals
+    );
+    binary_expr!(details, data_type, LessExpression, BinaryOp::Less);
+    binary_expr!(
+        details,
+        data_type,
+        LessEqualsExpression,
+        BinaryOp::LessEquals
+    );
+
+    // Arithmetic
+    binary_expr!(details, data_type, AddExpression, BinaryOp::Add);
+    binary_expr!(details, data_type, SubExpression, BinaryOp::Sub);
+    binary_expr!(details, data_type, MulExpression, BinaryOp::Multiply);
+    binary_expr!(details, data_type, DivExpression, BinaryOp::Divide);
+    binary_expr!(details, data_type, ModExpression, BinaryOp::Remainder);
+    binary_expr!(details, data_type, PowExpression, BinaryOp::Power);
+
+    Err(DeserializeError::Unsupported(format!(
+        "expression {}",
+        details.type_url
+    )))
+}
+
+/// Deserializes a field access expression. If the field is wrapped in a rename expression the
+/// returned field carries the new name as projected name.
+pub fn deserialize_field(expr: &SerializableExpression) -> Result<Field, DeserializeError> {
+    let details = required(expr.details.as_ref(), "details of expression")?;
+    if type_url_is::<FieldRenameExpression>(details) {
+        let rename: FieldRenameExpression = details.to_msg()?;
+        let inner = required(
+            rename.original_field_access_expression,
+            "original field of rename expression",
+        )?;
+        return Ok(deserialize_field(&inner)?.rename(rename.new_field_name));
+    }
+    if type_url_is::<FieldAccessExpression>(details) {
+        let access: FieldAccessExpression = details.to_msg()?;
+        return deserialize_field_access(&access);
+    }
+    Err(DeserializeError::Unsupported(format!(
+        "field expression {}",
+        details.type_url
+    )))
+}
+
+pub fn deserialize_field_access(access: &FieldAccessExpression) -> Result<Field, DeserializeError> {
+    let data_type = optional_data_type(&access.r#type)?;
+    Ok(Field::typed(&access.field_name, data_type))
+}
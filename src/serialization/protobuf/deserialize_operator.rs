@@ -0,0 +1,191 @@
+use std::collections::{HashMap, HashSet};
+
+use prost_types::Any;
+
+use crate::expression::{ArithmeticExpr, LogicalExpr};
+use crate::query::{
+    join::Join,
+    operator::{
+        Filter, Limit, Map, Operator, Projection, Rename, Union, WatermarkAssigner, Window,
+    },
+};
+
+use super::{
+    deserialize_error::DeserializeError,
+    deserialize_expression::{
+        deserialize_expression, deserialize_field, deserialize_field_access, required, type_url_is,
+    },
+    deserialize_source::deserialize_source_details,
+    deserialize_window::{
+        deserialize_aggregations, deserialize_allowed_lateness, deserialize_watermark_strategy,
+        deserialize_window_descriptor, deserialize_window_keys,
+    },
+    nes::{
+        serializable_expression::FieldAssignmentExpression,
+        serializable_operator::{
+            FilterDetails, JoinDetails, LimitDetails, MapDetails, ProjectionDetails, RenameDetails,
+            SourceDetails, UnionDetails, WatermarkStrategyDetails, WindowDetails,
+        },
+        SerializableOperator,
+    },
+};
+
+/// Upper bound for the number of operators of a plan. Operators are deserialized recursively, so
+/// this also bounds the recursion depth.
+const MAX_OPERATORS: usize = 1000;
+
+/// Rebuilds the operator tree rooted at `operator_id`. The first child of an operator is its
+/// child operator, the second child of a join or union is the joined operator tree. Fails if an
+/// operator is reachable more than once, e.g. because of a cycle, or does not have exactly the
+/// children it uses: none for sources, two for joins and unions and one for all other operators.
+pub fn deserialize_operator(
+    operator_id: u64,
+    map: &HashMap<u64, SerializableOperator>,
+) -> Result<Operator, DeserializeError> {
+    deserialize_operator_tree(operator_id, map, &mut HashSet::new())
+}
+
+fn deserialize_operator_tree(
+    operator_id: u64,
+    map: &HashMap<u64, SerializableOperator>,
+    visited: &mut HashSet<u64>,
+) -> Result<Operator, DeserializeError> {
+    if !visited.insert(operator_id) {
+        return Err(DeserializeError::InvalidPlan(format!(
+            "operator {operator_id} is referenced more than once"
+        )));
+    }
+    if visited.len() > MAX_OPERATORS {
+        return Err(DeserializeError::InvalidPlan(format!(
+            "more than {MAX_OPERATORS} operators"
+        )));
+    }
+    let operator = required(map.get(&operator_id), &format!("operator {operator_id}"))?;
+    let details = required(operator.details.as_ref(), "details of operator")?;
+    let used_children = if type_url_is::<SourceDetails>(details) {
+        0
+    } else if type_url_is::<JoinDetails>(details) || type_url_is::<UnionDetails>(details) {
+        2
+    } else {
+        1
+    };
+    if operator.children_ids.len() != used_children {
+        return Err(DeserializeError::InvalidPlan(format!(
+            "operator {operator_id} has {} children but expects {used_children}",
+            operator.children_ids.len()
+        )));
+    }
+    let mut children = Vec::new();
+    for id in &operator.children_ids {
+        children.push(Box::new(deserialize_operator_tree(*id, map, visited)?));
+    }
+    let mut children = children.into_iter();
+    let child = children.next();
+    let mut joined = || {
+        children
+            .next()
+            .ok_or_else(|| DeserializeError::MissingField(format!("joined child of {operator_id}")))
+    };
+
+    if type_url_is::<SourceDetails>(details) {
+        return deserialize_source_details(details);
+    }
+    if type_url_is::<ProjectionDetails>(details) {
+        let projection: ProjectionDetails = details.to_msg()?;
+        let fields = projection
+            .expression
+            .iter()
+            .map(deserialize_field)
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(Operator::Projection(Projection { fields, child }));
+    }
+    if type_url_is::<FilterDetails>(details) {
+        let filter: FilterDetails = details.to_msg()?;
+        let predicate = required(filter.predicate, "predicate of filter")?;
+        return Ok(Operator::Filter(Filter {
+            expression: LogicalExpr(deserialize_expression(&predicate)?),
+            child,
+        }));
+    }
+    if type_url_is::<MapDetails>(details) {
+        return map_operator(details, child);
+    }
+    if type_url_is::<WindowDetails>(details) {
+        let window: WindowDetails = details.to_msg()?;
+        let window_type = required(window.window_type.as_ref(), "type of window")?;
+        return Ok(Operator::Window(Window {
+            descriptor: deserialize_window_descriptor(window_type)?,
+            aggregations: deserialize_aggregations(&window.window_aggregations)?,
+            key_fields: deserialize_window_keys(&window.keys)?,
+            // A lateness of 0 is the default of the message, i.e. not set.
+            allowed_lateness: match window.allowed_lateness {
+                0 => None,
+                milliseconds => Some(deserialize_allowed_lateness(milliseconds)?),
+            },
+            child,
+        }));
+    }
+    if type_url_is::<JoinDetails>(details) {
+        let join: JoinDetails = details.to_msg()?;
+        let window_type = required(join.window_type.as_ref(), "window of join")?;
+        let lhs = required(join.on_left_key.as_ref(), "left key of join")?;
+        let rhs = required(join.on_right_key.as_ref(), "right key of join")?;
+        return Ok(Operator::Join(Join {
+            lhs: deserialize_field(lhs)?,
+            rhs: deserialize_field(rhs)?,
+            window: deserialize_window_descriptor(window_type)?,
+            joined_operators: joined()?,
+            child,
+        }));
+    }
+    if type_url_is::<UnionDetails>(details) {
+        return Ok(Operator::Union(Union {
+            operators: joined()?,
+            child,
+        }));
+    }
+    if type_url_is::<WatermarkStrategyDetails>(details) {
+        return Ok(Operator::WatermarkAssigner(WatermarkAssigner {
+            strategy: deserialize_watermark_strategy(details)?,
+            child,
+        }));
+    }
+    if type_url_is::<LimitDetails>(details) {
+        let limit: LimitDetails = details.to_msg()?;
+        return Ok(Operator::Limit(Limit {
+            limit: limit.limit,
+            child,
+        }));
+    }
+    if type_url_is::<RenameDetails>(details) {
+        let rename: RenameDetails = details.to_msg()?;
+        return Ok(Operator::Rename(Rename {
+            new_source_name: rename.new_source_name,
+            child,
+        }));
+    }
+    Err(DeserializeError::Unsupported(format!(
+        "operator {}",
+        details.type_url
+    )))
+}
+
+fn map_operator(details: &Any, child: Option<Box<Operator>>) -> Result<Operator, DeserializeError> {
+    let map: MapDetails = details.to_msg()?;
+    let expression = required(map.expression, "expression of map")?;
+    let assignment = required(expression.details, "details of map expression")?;
+    if !type_url_is::<FieldAssignmentExpression>(&assignment) {
+        return Err(DeserializeError::Unsupported(format!(
+            "map expression {}",
+            assignment.type_url
+        )));
+    }
+    let assignment: FieldAssignmentExpression = assignment.to_msg()?;
+    let field = required(assignment.field, "field of assignment")?;
+    let expression = required(assignment.assignment, "expression of assignment")?;
+    Ok(Operator::Map(Map {
+        assigned_field: deserialize_field_access(&field)?.name().to_string(),
+        expression: ArithmeticExpr(deserialize_expression(&expression)?),
+        child,
+    }))
+}
@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+
+use prost::Message;
+
+use crate::query::Query;
+
+use super::{
+    deserialize_error::DeserializeError,
+    deserialize_expression::required,
+    deserialize_operator::deserialize_operator,
+    deserialize_sink::{deserialize_sink_details, is_sink},
+    nes::{SerializableQueryPlan, SubmitQueryRequest},
+};
+
+/// Rebuilds a `Query` from a query plan. The plan must have a single root operator which is a
+/// sink with a single child.
+pub fn deserialize_query(query_plan: &SerializableQueryPlan) -> Result<Query, DeserializeError> {
+    log::debug!(
+        "Deserializing query plan with {} operators",
+        query_plan.operator_map.len()
+    );
+    let [root_id] = query_plan.root_operator_ids[..] else {
+        return Err(DeserializeError::Unsupported(format!(
+            "query plan with {} root operators",
+            query_plan.root_operator_ids.len()
+        )));
+    };
+    let root = required(
+        query_plan.operator_map.get(&root_id),
+        &format!("operator {root_id}"),
+    )?;
+    let details = required(root.details.as_ref(), "details of root operator")?;
+    if !is_sink(details) {
+        return Err(DeserializeError::Unsupported(format!(
+            "root operator {}",
+            details.type_url
+        )));
+    }
+    let sink = deserialize_sink_details(details)?;
+    let [child_id] = root.children_ids[..] else {
+        return Err(DeserializeError::Unsupported(format!(
+            "sink with {} children",
+            root.children_ids.len()
+        )));
+    };
+    let operator = deserialize_operator(child_id, &query_plan.operator_map)?;
+    Ok(Query::new(operator, sink))
+}
+
+/// Decodes an encoded `SerializableQueryPlan` message into a `Query`.
+pub fn decode_query_plan(bytes: &[u8]) -> Result<Query, DeserializeError> {
+    let query_plan = SerializableQueryPlan::decode(bytes)?;
+    deserialize_query(&query_plan)
+}
+
+/// Decodes an encoded `SubmitQueryRequest` into the submitted query and its context, e.g., the
+/// placement strategy under `placement`.
+pub fn decode_request(bytes: &[u8]) -> Result<(Query, HashMap<String, String>), DeserializeError> {
+    let request = SubmitQueryRequest::decode(bytes)?;
+    let query_plan = required(request.query_plan, "query plan of request")?;
+    let context = request
+        .context
+        .into_iter()
+        .map(|(key, value)| (key, String::from_utf8_lossy(&value.value).into_owned()))
+        .collect();
+    Ok((deserialize_query(&query_plan)?, context))
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+    use prost_types::Any;
+
+    use std::collections::HashMap;
+
+    use nes_types::{FloatType, IntType, NesType};
+
+    use super::{decode_query_plan, decode_request, deserialize_query};
+    use crate::{
+        prelude::{ExprBuilder as EB, *},
+        query::{
+            operator::Operator,
+            stringify::stringify_query,
+            time::{Duration, TimeCharacteristic, TimeUnit, WatermarkStrategy},
+        },
+        serialization::protobuf::{
+            deserialize_error::DeserializeError,
+            nes::serializable_operator::{TumblingWindow, WindowDetails},
+            serialize_query::{serialize_query, serialize_request_with_context},
+        },
+    };
+
+    fn assert_round_trip(query: Query) {
+        let bytes = serialize_query(&query).encode_to_vec();
+        let deser = decode_query_plan(&bytes).unwrap();
+        assert_eq!(stringify_query(&query), stringify_query(&deser));
+    }
+
+    #[test]
+    fn test_filter_map_round_trip() {
+        let query = QueryBuilder::from_source("test")
+            .filter(
+                EB::field("value")
+                    .greater_than(EB::literal(0))
+                    .and(EB::field("id").equals(EB::literal(1)))
+                    .build_logical()
+                    .unwrap(),
+            )
+            .map(
+                "doubled",
+                (EB::field("value") * EB::literal(2)).build_arith().unwrap(),
+            )
+            .sink(Sink::csv_file("result.csv", false));
+        assert_round_trip(query);
+    }
+
+    #[test]
+    fn test_window_join_union_round_trip() {
+        let time_character = TimeCharacteristic::event_time("ts", TimeUnit::Milliseconds);
+        let query = QueryBuilder::from_source("orders")
+            .union(QueryBuilder::from_source("archived_orders"))
+            .join_with(QueryBuilder::from_source("products"))
+            .where_field("product_id")
+            .equals("id")
+            .window(WindowDescriptor::tumbling(
+                Duration::from_seconds(10),
+                time_character.clone(),
+            ))
+            .window(WindowDescriptor::sliding(
+                Duration::from_minutes(10),
+                Duration::from_minutes(1),
+                time_character,
+            ))
+            .by_key("product_id")
+            .allowed_lateness(Duration::from_seconds(5))
+            .apply([
+                Aggregation::sum("price").as_field("total"),
+                Aggregation::count(),
+            ])
+            .sink(Sink::Print);
+        assert_round_trip(query);
+    }
+
+    #[test]
+    fn test_rename_limit_round_trip() {
+        let query = QueryBuilder::from_source("orders")
+            .rename("lhs")
+            .join_with(QueryBuilder::from_source("orders").rename("rhs"))
+            .where_field("lhs$id")
+            .equals("rhs$id")
+            .window(WindowDescriptor::tumbling(
+                Duration::from_seconds(10),
+                TimeCharacteristic::ingestion_time(),
+            ))
+            .limit(10)
+            .sink(Sink::Print);
+        assert_round_trip(query);
+    }
+
+    #[test]
+    fn test_watermark_round_trip() {
+        let query = QueryBuilder::from_source("test")
+            .assign_watermark(WatermarkStrategy::event_time(
+                "ts",
+                Duration::from_minutes(2),
+                TimeUnit::Seconds,
+            ))
+            .union(
+                QueryBuilder::from_source("other")
+                    .assign_watermark(WatermarkStrategy::ingestion_time()),
+            )
+            .sink(Sink::Print);
+        assert_round_trip(query);
+    }
+
+    #[test]
+    fn test_sink_round_trip() {
+        let sinks = [
+            Sink::kafka("localhost:9092", "results", 10000),
+            Sink::mqtt(
+                "tcp://localhost:1883",
+                "nes",
+                "results",
+                MqttQualityOfService::ExactlyOnce,
+            ),
+            Sink::zmq("localhost", 5555),
+            Sink::Opc {
+                url: "opc.tcp://localhost:4840".to_string(),
+                namespace_index: 1,
+                identifier: "results".to_string(),
+                identifier_type: OpcIdentifierType::Guid,
+                user: "nes".to_string(),
+                password: Password::new("secret"),
+            },
+        ];
+        for sink in sinks {
+            let query = QueryBuilder::from_source("test").sink(sink.clone());
+            let bytes = serialize_query(&query).encode_to_vec();
+            assert_eq!(&sink, decode_query_plan(&bytes).unwrap().sink());
+        }
+    }
+
+    #[test]
+    fn test_source_round_trip() {
+        let query = QueryBuilder::from_physical_source("test", "test_1").sink(Sink::Print);
+        assert_round_trip(query);
+
+        let schema = Schema::new()
+            .with_field("id", NesType::Int(IntType::Unsigned64))
+            .with_field("value", NesType::Float(FloatType::Bit64));
+        let descriptors = [
+            SourceDescriptor::csv("input.csv"),
+            SourceDescriptor::default_source(10),
+            SourceDescriptor::kafka("localhost:9092", "input", "nes"),
+            SourceDescriptor::mqtt("tcp://localhost:1883", "nes", "input", InputFormat::Json),
+            SourceDescriptor::tcp("localhost", 8080, InputFormat::Csv),
+            SourceDescriptor::zmq("localhost", 5555),
+        ];
+        for descriptor in descriptors {
+            let source = InlineSource {
+                name: "input".to_string(),
+                schema: schema.clone(),
+                descriptor,
+            };
+            let query = QueryBuilder::from_inline_source(
+                &source.name,
+                source.schema.clone(),
+                source.descriptor.clone(),
+            )
+            .sink(Sink::Print);
+            let bytes = serialize_query(&query).encode_to_vec();
+            match decode_query_plan(&bytes).unwrap().operator() {
+                Operator::InlineSource(deser) => assert_eq!(&source, deser),
+                operator => panic!("expected an inline source, found {operator}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_plans() {
+        let query = QueryBuilder::from_source("test")
+            .filter(
+                EB::field("id")
+                    .equals(EB::literal(1))
+                    .build_logical()
+                    .unwrap(),
+            )
+            .filter(
+                EB::field("id")
+                    .equals(EB::literal(2))
+                    .build_logical()
+                    .unwrap(),
+            )
+            .sink(Sink::Print);
+        let plan = serialize_query(&query);
+        let root = plan.root_operator_ids[0];
+        let outer = plan.operator_map[&root].children_ids[0];
+        let inner = plan.operator_map[&outer].children_ids[0];
+
+        // The inner filter points back to the outer filter.
+        let mut cyclic = plan.clone();
+        cyclic.operator_map.get_mut(&inner).unwrap().children_ids = vec![outer];
+        assert!(matches!(
+            deserialize_query(&cyclic),
+            Err(DeserializeError::InvalidPlan(_))
+        ));
+
+        let mut self_reference = plan.clone();
+        self_reference
+            .operator_map
+            .get_mut(&outer)
+            .unwrap()
+            .children_ids = vec![outer];
+        assert!(matches!(
+            deserialize_query(&self_reference),
+            Err(DeserializeError::InvalidPlan(_))
+        ));
+
+        let mut extra_child = plan.clone();
+        let source = extra_child.operator_map[&inner].children_ids[0];
+        extra_child
+            .operator_map
+            .get_mut(&outer)
+            .unwrap()
+            .children_ids
+            .push(source);
+        assert!(matches!(
+            deserialize_query(&extra_child),
+            Err(DeserializeError::InvalidPlan(_))
+        ));
+
+        let mut missing_child = plan;
+        missing_child
+            .operator_map
+            .get_mut(&inner)
+            .unwrap()
+            .children_ids
+            .clear();
+        assert!(matches!(
+            deserialize_query(&missing_child),
+            Err(DeserializeError::InvalidPlan(_))
+        ));
+
+        let query = QueryBuilder::from_source("test")
+            .union(QueryBuilder::from_source("other"))
+            .sink(Sink::Print);
+        let mut missing_joined = serialize_query(&query);
+        let root = missing_joined.root_operator_ids[0];
+        let union = missing_joined.operator_map[&root].children_ids[0];
+        missing_joined
+            .operator_map
+            .get_mut(&union)
+            .unwrap()
+            .children_ids
+            .pop();
+        assert!(matches!(
+            deserialize_query(&missing_joined),
+            Err(DeserializeError::InvalidPlan(_))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_time_characteristic() {
+        let query = QueryBuilder::from_source("test")
+            .window(WindowDescriptor::tumbling(
+                Duration::from_seconds(10),
+                TimeCharacteristic::event_time("ts", TimeUnit::Milliseconds),
+            ))
+            .apply([Aggregation::count()])
+            .sink(Sink::Print);
+        let mut plan = serialize_query(&query);
+        let root = plan.root_operator_ids[0];
+        let window = plan.operator_map[&root].children_ids[0];
+        let details = plan
+            .operator_map
+            .get_mut(&window)
+            .unwrap()
+            .details
+            .as_mut()
+            .unwrap();
+        let mut window_details: WindowDetails = details.to_msg().unwrap();
+        let mut tumbling: TumblingWindow = window_details
+            .window_type
+            .as_ref()
+            .unwrap()
+            .to_msg()
+            .unwrap();
+        tumbling.time_characteristic.as_mut().unwrap().r#type = 42;
+        window_details.window_type = Some(Any::from_msg(&tumbling).unwrap());
+        *details = Any::from_msg(&window_details).unwrap();
+        assert!(matches!(
+            deserialize_query(&plan),
+            Err(DeserializeError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_request_context() {
+        let query = QueryBuilder::from_source("test").sink(Sink::Print);
+        let context = HashMap::from([
+            ("experiment".to_string(), "placement-1".to_string()),
+            ("placement".to_string(), "TopDown".to_string()),
+        ]);
+        let bytes = serialize_request_with_context(
+            &query,
+            PlacementStrategy::Elegant(ElegantObjective::Energy),
+            &context,
+        );
+        let (deser, context) = decode_request(&bytes).unwrap();
+        assert_eq!(stringify_query(&query), stringify_query(&deser));
+        assert_eq!("ELEGANT_ENERGY", context["placement"]);
+        assert_eq!("placement-1", context["experiment"]);
+    }
+}
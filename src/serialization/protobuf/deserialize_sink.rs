@@ -0,0 +1,132 @@
+use prost_types::Any;
+
+use crate::query::sink::{
+    MqttQualityOfService, MqttTimeUnit, OpcIdentifierType, Password, Sink, SinkFormat,
+};
+
+use super::{
+    deserialize_error::DeserializeError,
+    deserialize_expression::{required, type_url_is},
+    nes::serializable_operator::{
+        sink_details::{
+            serializable_mqtt_sink_descriptor::{ServiceQualities, TimeUnits},
+            SerializableFileSinkDescriptor, SerializableKafkaSinkDescriptor,
+            SerializableMqttSinkDescriptor, SerializableNullOutputSinkDescriptor,
+            SerializableOpcSinkDescriptor, SerializablePrintSinkDescriptor,
+            SerializableZmqSinkDescriptor,
+        },
+        SinkDetails,
+    },
+};
+
+pub fn is_sink(details: &Any) -> bool {
+    type_url_is::<SinkDetails>(details)
+}
+
+pub fn deserialize_sink_details(details: &Any) -> Result<Sink, DeserializeError> {
+    let details: SinkDetails = details.to_msg()?;
+    let descriptor = required(details.sink_descriptor, "descriptor of sink")?;
+    if type_url_is::<SerializableNullOutputSinkDescriptor>(&descriptor) {
+        return Ok(Sink::NullOutput);
+    }
+    if type_url_is::<SerializablePrintSinkDescriptor>(&descriptor) {
+        return Ok(Sink::Print);
+    }
+    if type_url_is::<SerializableFileSinkDescriptor>(&descriptor) {
+        let file: SerializableFileSinkDescriptor = descriptor.to_msg()?;
+        let Some(format) = SinkFormat::from_coordinator_str(&file.sink_format) else {
+            return Err(DeserializeError::Unsupported(format!(
+                "sink format {}",
+                file.sink_format
+            )));
+        };
+        return Ok(Sink::File {
+            path: file.file_path,
+            format,
+            append: file.append,
+            add_timestamp: file.add_timestamp,
+        });
+    }
+    if type_url_is::<SerializableKafkaSinkDescriptor>(&descriptor) {
+        let kafka: SerializableKafkaSinkDescriptor = descriptor.to_msg()?;
+        return Ok(Sink::Kafka {
+            brokers: kafka.brokers,
+            topic: kafka.topic,
+            connect_timeout: kafka.kafka_connect_timeout,
+        });
+    }
+    if type_url_is::<SerializableMqttSinkDescriptor>(&descriptor) {
+        let mqtt: SerializableMqttSinkDescriptor = descriptor.to_msg()?;
+        return Ok(Sink::Mqtt {
+            time_unit: deserialize_time_unit(mqtt.time_unit)?,
+            quality_of_service: deserialize_service_quality(mqtt.quality_of_service)?,
+            address: mqtt.address,
+            client_id: mqtt.client_id,
+            topic: mqtt.topic,
+            user: mqtt.user,
+            max_buffered_messages: mqtt.max_buffered_ms_gs,
+            message_delay: mqtt.msg_delay,
+            asynchronous_client: mqtt.asynchronous_client,
+        });
+    }
+    if type_url_is::<SerializableZmqSinkDescriptor>(&descriptor) {
+        let zmq: SerializableZmqSinkDescriptor = descriptor.to_msg()?;
+        return Ok(Sink::Zmq {
+            host: zmq.host,
+            port: zmq.port,
+        });
+    }
+    if type_url_is::<SerializableOpcSinkDescriptor>(&descriptor) {
+        let opc: SerializableOpcSinkDescriptor = descriptor.to_msg()?;
+        return Ok(Sink::Opc {
+            identifier_type: deserialize_identifier_type(opc.identifier_type)?,
+            url: opc.url,
+            namespace_index: opc.namespace_index,
+            identifier: opc.identifier,
+            user: opc.user,
+            password: Password::new(opc.password),
+        });
+    }
+    Err(DeserializeError::Unsupported(format!(
+        "sink descriptor {}",
+        descriptor.type_url
+    )))
+}
+
+fn deserialize_time_unit(time_unit: i32) -> Result<MqttTimeUnit, DeserializeError> {
+    match TimeUnits::try_from(time_unit) {
+        Ok(TimeUnits::Nanoseconds) => Ok(MqttTimeUnit::Nanoseconds),
+        Ok(TimeUnits::Milliseconds) => Ok(MqttTimeUnit::Milliseconds),
+        Ok(TimeUnits::Seconds) => Ok(MqttTimeUnit::Seconds),
+        Err(_) => Err(DeserializeError::Unsupported(format!(
+            "MQTT time unit {time_unit}"
+        ))),
+    }
+}
+
+fn deserialize_service_quality(
+    quality_of_service: i32,
+) -> Result<MqttQualityOfService, DeserializeError> {
+    match ServiceQualities::try_from(quality_of_service) {
+        Ok(ServiceQualities::AtMostOnce) => Ok(MqttQualityOfService::AtMostOnce),
+        Ok(ServiceQualities::AtLeastOnce) => Ok(MqttQualityOfService::AtLeastOnce),
+        Ok(ServiceQualities::ExactlyOnce) => Ok(MqttQualityOfService::ExactlyOnce),
+        Err(_) => Err(DeserializeError::Unsupported(format!(
+            "MQTT quality of service {quality_of_service}"
+        ))),
+    }
+}
+
+fn deserialize_identifier_type(
+    identifier_type: u32,
+) -> Result<OpcIdentifierType, DeserializeError> {
+    match identifier_type {
+        0 => Ok(OpcIdentifierType::Numeric),
+        3 => Ok(OpcIdentifierType::String),
+        4 => Ok(OpcIdentifierType::Guid),
+        5 => Ok(OpcIdentifierType::ByteString),
+        _ => Err(DeserializeError::Unsupported(format!(
+            "OPC identifier type {identifier_type}"
+        ))),
+    }
+}
@@ -0,0 +1,217 @@
+use prost_types::Any;
+
+use crate::query::{
+    operator::Operator,
+    schema::Schema,
+    source::{InlineSource, InputFormat, SourceDescriptor},
+};
+
+use super::{
+    deserialize_error::DeserializeError,
+    deserialize_expression::{required, type_url_is},
+    nes::{
+        serializable_operator::{
+            source_details::{
+                SerializableArrowSourceDescriptor, SerializableBinarySourceDescriptor,
+                SerializableCsvSourceDescriptor, SerializableDefaultSourceDescriptor,
+                SerializableKafkaSourceDescriptor, SerializableLogicalSourceDescriptor,
+                SerializableMqttSourceDescriptor, SerializableTcpSourceDescriptor,
+                SerializableZmqSourceDescriptor,
+            },
+            SourceDetails,
+        },
+        serializable_physical_source_type::{
+            InputFormat as SerializableInputFormat, SerializableArrowSourceType,
+            SerializableCsvSourceType, SerializableMqttSourceType, SerializableTcpSourceType,
+        },
+        SerializablePhysicalSourceType, SerializableSchema,
+    },
+    serialize_schema::deserialize_schema,
+};
+
+/// Deserializes a source operator. Logical source descriptors become `Operator::LogicalSource`,
+/// all other supported descriptors become `Operator::InlineSource`.
+pub fn deserialize_source_details(details: &Any) -> Result<Operator, DeserializeError> {
+    let source: SourceDetails = details.to_msg()?;
+    let descriptor = required(source.source_descriptor, "descriptor of source")?;
+    if type_url_is::<SerializableLogicalSourceDescriptor>(&descriptor) {
+        let descriptor: SerializableLogicalSourceDescriptor = descriptor.to_msg()?;
+        let physical_source_name =
+            Some(descriptor.physical_source_name).filter(|name| !name.is_empty());
+        return Ok(Operator::LogicalSource {
+            source_name: descriptor.logical_source_name,
+            physical_source_name,
+        });
+    }
+    if type_url_is::<SerializableCsvSourceDescriptor>(&descriptor) {
+        let csv: SerializableCsvSourceDescriptor = descriptor.to_msg()?;
+        let (name, source_type): (_, SerializableCsvSourceType) =
+            physical_source_type(csv.physical_source_type)?;
+        return inline_source(
+            name,
+            csv.source_schema,
+            SourceDescriptor::Csv {
+                path: source_type.file_path,
+                skip_header: source_type.skip_header,
+                delimiter: source_type.delimiter,
+                number_of_buffers_to_produce: source_type.number_of_buffers_to_produce,
+                number_of_tuples_to_produce_per_buffer: source_type
+                    .number_of_tuples_to_produce_per_buffer,
+                gathering_interval: source_type.source_gathering_interval,
+            },
+        );
+    }
+    if type_url_is::<SerializableBinarySourceDescriptor>(&descriptor) {
+        let binary: SerializableBinarySourceDescriptor = descriptor.to_msg()?;
+        return inline_source(
+            None,
+            binary.source_schema,
+            SourceDescriptor::Binary {
+                path: binary.file_path,
+            },
+        );
+    }
+    if type_url_is::<SerializableDefaultSourceDescriptor>(&descriptor) {
+        let default: SerializableDefaultSourceDescriptor = descriptor.to_msg()?;
+        return inline_source(
+            None,
+            default.source_schema,
+            SourceDescriptor::Default {
+                number_of_buffers_to_produce: default.number_of_buffers_to_produce,
+                gathering_interval: default.source_gathering_interval,
+            },
+        );
+    }
+    if type_url_is::<SerializableKafkaSourceDescriptor>(&descriptor) {
+        let kafka: SerializableKafkaSourceDescriptor = descriptor.to_msg()?;
+        return inline_source(
+            None,
+            kafka.source_schema,
+            SourceDescriptor::Kafka {
+                brokers: kafka.brokers,
+                topic: kafka.topic,
+                group_id: kafka.group_id,
+                auto_commit: kafka.auto_commit,
+                connect_timeout: kafka.kafka_connect_timeout,
+            },
+        );
+    }
+    if type_url_is::<SerializableMqttSourceDescriptor>(&descriptor) {
+        let mqtt: SerializableMqttSourceDescriptor = descriptor.to_msg()?;
+        let (name, source_type): (_, SerializableMqttSourceType) =
+            physical_source_type(mqtt.physical_source_type)?;
+        return inline_source(
+            name,
+            mqtt.source_schema,
+            SourceDescriptor::Mqtt {
+                input_format: deserialize_input_format(source_type.input_format)?,
+                url: source_type.url,
+                client_id: source_type.client_id,
+                user: source_type.user_name,
+                topic: source_type.topic,
+                quality_of_service: source_type.qos,
+                clean_session: source_type.clean_session,
+                flush_interval_ms: source_type.flush_interval_ms,
+            },
+        );
+    }
+    if type_url_is::<SerializableTcpSourceDescriptor>(&descriptor) {
+        let tcp: SerializableTcpSourceDescriptor = descriptor.to_msg()?;
+        let (name, source_type): (_, SerializableTcpSourceType) =
+            physical_source_type(tcp.physical_source_type)?;
+        return inline_source(
+            name,
+            tcp.source_schema,
+            SourceDescriptor::Tcp {
+                input_format: deserialize_input_format(source_type.input_format)?,
+                host: source_type.socket_host,
+                port: source_type.socket_port,
+                tuple_separator: source_type.tuple_separator,
+                flush_interval_ms: source_type.flush_interval_ms,
+            },
+        );
+    }
+    if type_url_is::<SerializableZmqSourceDescriptor>(&descriptor) {
+        let zmq: SerializableZmqSourceDescriptor = descriptor.to_msg()?;
+        return inline_source(
+            None,
+            zmq.source_schema,
+            SourceDescriptor::Zmq {
+                host: zmq.host,
+                port: zmq.port,
+            },
+        );
+    }
+    if type_url_is::<SerializableArrowSourceDescriptor>(&descriptor) {
+        let arrow: SerializableArrowSourceDescriptor = descriptor.to_msg()?;
+        let (name, source_type): (_, SerializableArrowSourceType) =
+            physical_source_type(arrow.physical_source_type)?;
+        return inline_source(
+            name,
+            arrow.source_schema,
+            SourceDescriptor::Arrow {
+                path: source_type.file_path,
+                number_of_buffers_to_produce: source_type.number_of_buffers_to_produce,
+                number_of_tuples_to_produce_per_buffer: source_type
+                    .number_of_tuples_to_produce_per_buffer,
+                gathering_interval: source_type.source_gathering_interval,
+            },
+        );
+    }
+    Err(DeserializeError::Unsupported(format!(
+        "source descriptor {}",
+        descriptor.type_url
+    )))
+}
+
+/// Returns the logical source name and the specific type of a physical source type.
+fn physical_source_type<M: prost::Name + Default>(
+    source_type: Option<SerializablePhysicalSourceType>,
+) -> Result<(Option<String>, M), DeserializeError> {
+    let source_type = required(source_type, "physical source type")?;
+    let specific_type = required(
+        source_type.specific_physical_source_type,
+        "specific physical source type",
+    )?;
+    let name = Some(source_type.logical_source_name).filter(|name| !name.is_empty());
+    Ok((name, specific_type.to_msg()?))
+}
+
+/// Builds an inline source. Descriptors without a logical source name are named after the
+/// qualifier of their schema. The qualifier is removed from the fields of the schema.
+fn inline_source(
+    name: Option<String>,
+    schema: Option<SerializableSchema>,
+    descriptor: SourceDescriptor,
+) -> Result<Operator, DeserializeError> {
+    let qualified = deserialize_schema(&required(schema, "schema of source")?)?;
+    let name = name
+        .or_else(|| {
+            let field = qualified.fields().first()?;
+            let (qualifier, _) = field.name().split_once('$')?;
+            Some(qualifier.to_string())
+        })
+        .unwrap_or_default();
+    let prefix = format!("{name}$");
+    let mut schema = Schema::new();
+    for field in qualified.fields() {
+        let field_name = field.name().strip_prefix(&prefix).unwrap_or(field.name());
+        schema = schema.with_field(field_name, field.data_type());
+    }
+    Ok(Operator::InlineSource(InlineSource {
+        name,
+        schema,
+        descriptor,
+    }))
+}
+
+fn deserialize_input_format(input_format: i32) -> Result<InputFormat, DeserializeError> {
+    match SerializableInputFormat::try_from(input_format) {
+        Ok(SerializableInputFormat::Json) => Ok(InputFormat::Json),
+        Ok(SerializableInputFormat::Csv) => Ok(InputFormat::Csv),
+        Ok(SerializableInputFormat::NesBinary) => Ok(InputFormat::NesBinary),
+        Err(_) => Err(DeserializeError::Unsupported(format!(
+            "input format {input_format}"
+        ))),
+    }
+}
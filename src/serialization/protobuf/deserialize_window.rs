@@ -0,0 +1,186 @@
+use prost_types::Any;
+
+use crate::expression::LogicalExpr;
+use crate::query::{
+    time::{Duration, TimeCharacteristic, TimeUnit, WatermarkStrategy},
+    window::{aggregation::Aggregation, window_descriptor::WindowDescriptor},
+};
+
+use super::{
+    deserialize_error::DeserializeError,
+    deserialize_expression::{deserialize_expression, deserialize_field, required, type_url_is},
+    nes::{
+        serializable_operator::{
+            time_characteristic,
+            watermark_strategy_details::{
+                SerializableEventTimeWatermarkStrategyDescriptor,
+                SerializableIngestionTimeWatermarkStrategyDescriptor,
+            },
+            window_details::{aggregation::Type, Aggregation as SerializableAggregation},
+            SlidingWindow, ThresholdWindow, TimeCharacteristic as STimeCharacter, TumblingWindow,
+            WatermarkStrategyDetails,
+        },
+        SerializableExpression,
+    },
+};
+
+pub fn deserialize_window_descriptor(
+    window_type: &Any,
+) -> Result<WindowDescriptor, DeserializeError> {
+    if type_url_is::<TumblingWindow>(window_type) {
+        let window: TumblingWindow = window_type.to_msg()?;
+        let time_character = required(window.time_characteristic, "time characteristic")?;
+        return Ok(WindowDescriptor::TumblingWindow {
+            duration: deserialize_duration(window.size),
+            time_character: deserialize_time_characteristic(&time_character)?,
+        });
+    }
+    if type_url_is::<SlidingWindow>(window_type) {
+        let window: SlidingWindow = window_type.to_msg()?;
+        let time_character = required(window.time_characteristic, "time characteristic")?;
+        return Ok(WindowDescriptor::SlidingWindow {
+            size: deserialize_duration(window.size),
+            slide: deserialize_duration(window.slide),
+            time_character: deserialize_time_characteristic(&time_character)?,
+        });
+    }
+    if type_url_is::<ThresholdWindow>(window_type) {
+        let window: ThresholdWindow = window_type.to_msg()?;
+        let predicate = required(window.predicate, "predicate of threshold window")?;
+        return Ok(WindowDescriptor::ThresholdWindow {
+            predicate: LogicalExpr(deserialize_expression(&predicate)?),
+            minimum_count: window.minimum_count,
+        });
+    }
+    Err(DeserializeError::Unsupported(format!(
+        "window type {}",
+        window_type.type_url
+    )))
+}
+
+pub fn deserialize_watermark_strategy(
+    details: &Any,
+) -> Result<WatermarkStrategy, DeserializeError> {
+    let details: WatermarkStrategyDetails = details.to_msg()?;
+    let strategy = required(details.strategy, "watermark strategy")?;
+    if type_url_is::<SerializableEventTimeWatermarkStrategyDescriptor>(&strategy) {
+        let strategy: SerializableEventTimeWatermarkStrategyDescriptor = strategy.to_msg()?;
+        let field = required(strategy.on_field.as_ref(), "field of watermark strategy")?;
+        return Ok(WatermarkStrategy::EventTime {
+            field_name: deserialize_field(field)?.name().to_string(),
+            allowed_lateness: deserialize_allowed_lateness(strategy.allowed_lateness)?,
+            unit: deserialize_time_unit(strategy.multiplier)?,
+        });
+    }
+    if type_url_is::<SerializableIngestionTimeWatermarkStrategyDescriptor>(&strategy) {
+        return Ok(WatermarkStrategy::IngestionTime);
+    }
+    Err(DeserializeError::Unsupported(format!(
+        "watermark strategy {}",
+        strategy.type_url
+    )))
+}
+
+pub fn deserialize_allowed_lateness(milliseconds: u64) -> Result<Duration, DeserializeError> {
+    let milliseconds = u32::try_from(milliseconds).map_err(|_| {
+        DeserializeError::Unsupported(format!("allowed lateness of {milliseconds} ms"))
+    })?;
+    Ok(deserialize_duration(milliseconds))
+}
+
+/// Durations are serialized in milliseconds. Use the largest unit that represents the duration
+/// exactly, so that e.g. `Duration::from_seconds(10)` survives a round trip.
+fn deserialize_duration(milliseconds: u32) -> Duration {
+    let unit = [
+        TimeUnit::Days,
+        TimeUnit::Hours,
+        TimeUnit::Minutes,
+        TimeUnit::Seconds,
+    ]
+    .into_iter()
+    .find(|unit| milliseconds != 0 && milliseconds.is_multiple_of(unit.to_scalar()))
+    .unwrap_or(TimeUnit::Milliseconds);
+    Duration {
+        amount: milliseconds / unit.to_scalar(),
+        unit,
+    }
+}
+
+fn deserialize_time_unit(multiplier: u64) -> Result<TimeUnit, DeserializeError> {
+    [
+        TimeUnit::Milliseconds,
+        TimeUnit::Seconds,
+        TimeUnit::Minutes,
+        TimeUnit::Hours,
+        TimeUnit::Days,
+    ]
+    .into_iter()
+    .find(|unit| u64::from(unit.to_scalar()) == multiplier)
+    .ok_or_else(|| DeserializeError::Unsupported(format!("time unit multiplier {multiplier}")))
+}
+
+fn deserialize_time_characteristic(
+    time_character: &STimeCharacter,
+) -> Result<TimeCharacteristic, DeserializeError> {
+    match time_characteristic::Type::try_from(time_character.r#type) {
+        Ok(time_characteristic::Type::EventTime) => Ok(TimeCharacteristic::EventTime {
+            field_name: time_character.field.to_string(),
+            unit: deserialize_time_unit(time_character.multiplier)?,
+        }),
+        Ok(time_characteristic::Type::IngestionTime) => Ok(TimeCharacteristic::IngestionTime),
+        Err(_) => Err(DeserializeError::Unsupported(format!(
+            "time characteristic {}",
+            time_character.r#type
+        ))),
+    }
+}
+
+pub fn deserialize_aggregations(
+    aggregations: &[SerializableAggregation],
+) -> Result<Vec<Aggregation>, DeserializeError> {
+    aggregations.iter().map(deserialize_aggregation).collect()
+}
+
+fn deserialize_aggregation(
+    aggregation: &SerializableAggregation,
+) -> Result<Aggregation, DeserializeError> {
+    let on_field = deserialize_field(required(
+        aggregation.on_field.as_ref(),
+        "field of aggregation",
+    )?)?;
+    let as_field = match &aggregation.as_field {
+        Some(as_field) => Some(deserialize_field(as_field)?),
+        None => None,
+    };
+    let result = match Type::try_from(aggregation.r#type) {
+        Ok(Type::Sum) => Aggregation::sum(on_field.clone()),
+        Ok(Type::Avg) => Aggregation::average(on_field.clone()),
+        Ok(Type::Min) => Aggregation::min(on_field.clone()),
+        Ok(Type::Max) => Aggregation::max(on_field.clone()),
+        Ok(Type::Median) => Aggregation::median(on_field.clone()),
+        Ok(Type::Count) => Aggregation::count(),
+        Err(_) => {
+            return Err(DeserializeError::Unsupported(format!(
+                "aggregation type {}",
+                aggregation.r#type
+            )))
+        }
+    };
+    // The serializer sets `as_field` to `on_field` if no projected field is given.
+    match as_field {
+        Some(as_field) if as_field != on_field => Ok(result.as_field(as_field)),
+        _ => Ok(result),
+    }
+}
+
+pub fn deserialize_window_keys(
+    keys: &[SerializableExpression],
+) -> Result<Option<Vec<String>>, DeserializeError> {
+    if keys.is_empty() {
+        return Ok(None);
+    }
+    keys.iter()
+        .map(|key| deserialize_field(key).map(|field| field.name().to_string()))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
@@ -1,9 +1,18 @@
 pub mod serialize_query;
+pub mod serialize_schema;
+pub mod deserialize_error;
+pub mod deserialize_query;
 mod serialize_operator;
 mod serialize_sink;
 mod serialize_expression;
 mod serialize_data_type;
 mod serialize_window;
+mod serialize_source;
+mod deserialize_operator;
+mod deserialize_sink;
+mod deserialize_expression;
+mod deserialize_window;
+mod deserialize_source;
 
 
 pub(super) mod nes {
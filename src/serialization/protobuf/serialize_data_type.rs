@@ -1,8 +1,11 @@
 use prost_types::Any;
 
-use super::nes::{
-    serializable_data_type::{FloatDetails, IntegerDetails, Type},
-    SerializableDataType,
+use super::{
+    deserialize_error::DeserializeError,
+    nes::{
+        serializable_data_type::{FloatDetails, IntegerDetails, Type},
+        SerializableDataType,
+    },
 };
 use nes_types::{FloatType, IntType, NesType};
 
@@ -26,6 +29,73 @@ pub fn serialize_data_type(data_type: NesType) -> SerializableDataType {
     }
 }
 
+pub fn deserialize_data_type(
+    data_type: &SerializableDataType,
+) -> Result<NesType, DeserializeError> {
+    let Ok(serial_type) = Type::try_from(data_type.r#type) else {
+        return Err(DeserializeError::Unsupported(format!(
+            "data type {}",
+            data_type.r#type
+        )));
+    };
+    match serial_type {
+        Type::Undefined => Ok(NesType::Undefined),
+        Type::Char => Ok(NesType::Char),
+        Type::Boolean => Ok(NesType::Bool),
+        Type::Integer => {
+            let details: IntegerDetails = details_helper(data_type)?.to_msg()?;
+            // Unsigned types have a lower bound of zero and a non-zero upper bound (`u64::MAX`
+            // wraps to -1). Absent bounds are ambiguous and thus rejected.
+            let signed = match (details.lower_bound, details.upper_bound) {
+                (lower, _) if lower < 0 => true,
+                (0, upper) if upper != 0 => false,
+                (lower, upper) => {
+                    return Err(DeserializeError::Unsupported(format!(
+                        "integer with bounds {lower}..={upper}"
+                    )))
+                }
+            };
+            let int_type = match (details.bits, signed) {
+                (8, true) => IntType::Signed8,
+                (8, false) => IntType::Unsigned8,
+                (16, true) => IntType::Signed16,
+                (16, false) => IntType::Unsigned16,
+                (32, true) => IntType::Signed32,
+                (32, false) => IntType::Unsigned32,
+                (64, true) => IntType::Signed64,
+                (64, false) => IntType::Unsigned64,
+                (bits, _) => {
+                    return Err(DeserializeError::Unsupported(format!(
+                        "integer with {bits} bits"
+                    )))
+                }
+            };
+            Ok(NesType::Int(int_type))
+        }
+        Type::Float => {
+            let details: FloatDetails = details_helper(data_type)?.to_msg()?;
+            match details.bits {
+                32 => Ok(NesType::Float(FloatType::Bit32)),
+                64 => Ok(NesType::Float(FloatType::Bit64)),
+                bits => Err(DeserializeError::Unsupported(format!(
+                    "float with {bits} bits"
+                ))),
+            }
+        }
+        Type::Array | Type::Text => Err(DeserializeError::Unsupported(format!(
+            "data type {}",
+            serial_type.as_str_name()
+        ))),
+    }
+}
+
+fn details_helper(data_type: &SerializableDataType) -> Result<&Any, DeserializeError> {
+    data_type
+        .details
+        .as_ref()
+        .ok_or_else(|| DeserializeError::MissingField("details of data type".to_string()))
+}
+
 macro_rules! int_details {
     ($type:ty) => {
         IntegerDetails {
@@ -65,3 +135,40 @@ const fn float_detail_helper(float_type: FloatType) -> FloatDetails {
         FloatType::Bit64 => float_details!(u64),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_int_round_trip() {
+        for int_type in [
+            IntType::Signed8,
+            IntType::Unsigned8,
+            IntType::Signed16,
+            IntType::Unsigned16,
+            IntType::Signed32,
+            IntType::Unsigned32,
+            IntType::Signed64,
+            IntType::Unsigned64,
+        ] {
+            let data_type = NesType::Int(int_type);
+            let serialized = serialize_data_type(data_type);
+            assert_eq!(data_type, deserialize_data_type(&serialized).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_int_without_bounds() {
+        let details = IntegerDetails {
+            bits: 32,
+            upper_bound: 0,
+            lower_bound: 0,
+        };
+        let data_type = SerializableDataType {
+            r#type: Type::Integer.into(),
+            details: Some(Any::from_msg(&details).unwrap()),
+        };
+        assert!(deserialize_data_type(&data_type).is_err());
+    }
+}
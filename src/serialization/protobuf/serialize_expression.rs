@@ -4,11 +4,12 @@ use super::{
     nes::{
         serializable_data_value::BasicValue,
         serializable_expression::{
-            AbsExpression, AddExpression, AndExpression, ConstantValueExpression, DivExpression,
-            EqualsExpression, FieldAccessExpression, FieldAssignmentExpression,
-            FieldRenameExpression, GreaterEqualsExpression, GreaterExpression,
-            LessEqualsExpression, LessExpression, ModExpression, MulExpression, NegateExpression,
-            OrExpression, PowExpression, SubExpression,
+            AbsExpression, AddExpression, AndExpression, CaseExpression, CeilExpression,
+            ConstantValueExpression, DivExpression, EqualsExpression, ExpExpression,
+            FieldAccessExpression, FieldAssignmentExpression, FieldRenameExpression,
+            FloorExpression, GreaterEqualsExpression, GreaterExpression, LessEqualsExpression,
+            LessExpression, ModExpression, MulExpression, NegateExpression, OrExpression,
+            PowExpression, RoundExpression, SqrtExpression, SubExpression, WhenExpression,
         },
         SerializableDataValue, SerializableExpression,
     },
@@ -16,6 +17,7 @@ use super::{
 };
 use crate::expression::{
     binary_expression::{BinaryExpr, BinaryOp},
+    case_expression::CaseExpr,
     expression::RawExpr,
     field::Field,
     literal::Literal,
@@ -38,6 +40,7 @@ pub fn serialize_expression(expr: &RawExpr) -> SerializableExpression {
             serialize_expression(lhs),
             serialize_expression(rhs),
         ),
+        RawExpr::Case(case) => case_details(case),
     };
     SerializableExpression {
         details: Some(details),
@@ -122,10 +125,38 @@ fn unary_operator_details(operator: UnaryOp, child: SerializableExpression) -> p
     match operator {
         UnaryOp::Negate => unary_op!(child, NegateExpression),
         UnaryOp::Absolute => unary_op!(child, AbsExpression),
+        UnaryOp::Ceil => unary_op!(child, CeilExpression),
+        UnaryOp::Floor => unary_op!(child, FloorExpression),
+        UnaryOp::Round => unary_op!(child, RoundExpression),
+        UnaryOp::Sqrt => unary_op!(child, SqrtExpression),
+        UnaryOp::Exp => unary_op!(child, ExpExpression),
     }
     .unwrap()
 }
 
+fn case_details(case: &CaseExpr) -> prost_types::Any {
+    let whens = case
+        .branches
+        .iter()
+        .map(|branch| {
+            let when = WhenExpression {
+                left: Some(serialize_expression(&branch.condition)),
+                right: Some(serialize_expression(&branch.value)),
+            };
+            SerializableExpression {
+                details: Some(Any::from_msg(&when).unwrap()),
+                children: vec![],
+                stamp: Some(serialize_data_type(branch.value.data_type())),
+            }
+        })
+        .collect();
+    let expr = CaseExpression {
+        left: whens,
+        right: Some(serialize_expression(&case.default)),
+    };
+    Any::from_msg(&expr).unwrap()
+}
+
 macro_rules! binary_op {
     ($lhs:expr, $rhs:expr, $expr:ident) => {
         Any::from_msg(&$expr {
@@ -11,14 +11,16 @@ use super::nes::serializable_operator::ProjectionDetails;
 use super::{
     nes::{
         serializable_operator::{
-            source_details::SerializableLogicalSourceDescriptor, FilterDetails, JoinDetails,
-            MapDetails, SourceDetails, UnionDetails, WindowDetails,
+            FilterDetails, JoinDetails, LimitDetails, MapDetails, RenameDetails, UnionDetails,
+            WindowDetails,
         },
         SerializableOperator,
     },
     serialize_expression::{serialize_expression, serialize_field, serialize_field_assignment},
+    serialize_source::{inline_source_details, logical_source_details},
     serialize_window::{
-        serialize_aggregations, serialize_window_descriptor, serialize_window_keys,
+        serialize_aggregations, serialize_watermark_strategy, serialize_window_descriptor,
+        serialize_window_keys,
     },
 };
 
@@ -42,6 +44,7 @@ pub fn traverse_operators(
     }
     let joined_id = match op {
         Operator::Union(u) => traverse_operators(Some(&u.operators), id_counter, map),
+        Operator::Join(j) => traverse_operators(Some(&j.joined_operators), id_counter, map),
         _ => None,
     };
     if let Some(id) = joined_id {
@@ -67,30 +70,31 @@ pub fn traverse_operators(
 
 fn serialize_operator_details(operator: &Operator) -> prost_types::Any {
     match operator {
-        Operator::LogicalSource { source_name } => {
-            Any::from_msg(&logical_source_details(source_name))
-        }
+        Operator::LogicalSource {
+            source_name,
+            physical_source_name,
+        } => Any::from_msg(&logical_source_details(
+            source_name,
+            physical_source_name.as_deref(),
+        )),
+        Operator::InlineSource(source) => Any::from_msg(&inline_source_details(source)),
         Operator::Projection(projection) => Any::from_msg(&projection_details(projection)),
         Operator::Filter(filter) => Any::from_msg(&filter_details(filter)),
         Operator::Map(map) => Any::from_msg(&map_details(map)),
         Operator::Window(window) => Any::from_msg(&window_details(window)),
         Operator::Join(join) => Any::from_msg(&join_details(join)),
         Operator::Union(_) => Any::from_msg(&UnionDetails {}),
+        Operator::WatermarkAssigner(assigner) => {
+            Any::from_msg(&serialize_watermark_strategy(&assigner.strategy))
+        }
+        Operator::Limit(limit) => Any::from_msg(&LimitDetails { limit: limit.limit }),
+        Operator::Rename(rename) => Any::from_msg(&RenameDetails {
+            new_source_name: rename.new_source_name.clone(),
+        }),
     }
     .unwrap()
 }
 
-fn logical_source_details(source_name: &String) -> SourceDetails {
-    let descriptor = SerializableLogicalSourceDescriptor {
-        logical_source_name: source_name.to_string(),
-        ..Default::default()
-    };
-    let descriptor = Any::from_msg(&descriptor).unwrap();
-    SourceDetails {
-        source_descriptor: Some(descriptor),
-        ..Default::default()
-    }
-}
 fn projection_details(projection: &crate::query::operator::Projection) -> ProjectionDetails {
     let mut exprs = Vec::new();
     for field in &projection.fields {
@@ -127,6 +131,10 @@ fn window_details(window: &Window) -> WindowDetails {
             .key_fields
             .as_ref()
             .map_or(vec![], |keys| serialize_window_keys(keys)),
+        allowed_lateness: window
+            .allowed_lateness
+            .as_ref()
+            .map_or(0, |lateness| lateness.to_milliseconds().into()),
         ..Default::default()
     }
 }
@@ -138,8 +146,8 @@ fn join_details(join: &Join) -> JoinDetails {
         window_type: Some(serialize_window_descriptor(&join.window)),
         number_of_input_edges_left: 1,
         number_of_input_edges_right: 1,
-        left_source_type: Some(serialize_field(&join.lhs)),
-        right_source_type: Some(serialize_field(&join.rhs)),
+        on_left_key: Some(serialize_field(&join.lhs)),
+        on_right_key: Some(serialize_field(&join.rhs)),
         ..Default::default()
     }
 }
@@ -210,11 +218,7 @@ mod tests {
                     .unwrap(),
             )
             .union(query_sub)
-            .sink(Sink::File {
-                path: "./generated_files/result-1.csv".into(),
-                format: "CSV_FORMAT".into(),
-                append: false,
-            });
+            .sink(Sink::csv_file("./generated_files/result-1.csv", false));
         let mut id = 0;
         let mut operator_map = HashMap::new();
         traverse_operators(Some(query.operator()), &mut id, &mut operator_map);
@@ -222,4 +226,28 @@ mod tests {
         assert_eq!(6, id);
         // assert!(operator_map[0]., )
     }
+
+    #[test]
+    fn join_test() {
+        use crate::query::time::{Duration, TimeCharacteristic, TimeUnit};
+        let query = QueryBuilder::from_source("orders")
+            .join_with(QueryBuilder::from_source("products"))
+            .where_field("product_id")
+            .equals("id")
+            .window(WindowDescriptor::TumblingWindow {
+                duration: Duration::from_seconds(10),
+                time_character: TimeCharacteristic::EventTime {
+                    field_name: "ts".to_string(),
+                    unit: TimeUnit::Milliseconds,
+                },
+            })
+            .sink(Sink::Print);
+        let mut id = 0;
+        let mut operator_map = HashMap::new();
+        traverse_operators(Some(query.operator()), &mut id, &mut operator_map);
+        assert_eq!(3, id);
+        assert_eq!(vec![1, 2], operator_map[&0].children_ids);
+        assert!(operator_map[&1].children_ids.is_empty());
+        assert!(operator_map[&2].children_ids.is_empty());
+    }
 }
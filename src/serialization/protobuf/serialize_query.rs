@@ -29,13 +29,23 @@ pub fn serialize_query(query: &Query) -> SerializableQueryPlan {
 }
 
 pub fn serialize_request(query: &Query, placement: PlacementStrategy) -> Vec<u8> {
+    serialize_request_with_context(query, placement, &HashMap::new())
+}
+
+/// Serializes a submit request whose context contains the entries of `placement` and the
+/// additional `context` entries. The entries of `placement` take precedence over `context`.
+pub fn serialize_request_with_context(
+    query: &Query,
+    placement: PlacementStrategy,
+    context: &HashMap<String, String>,
+) -> Vec<u8> {
     let query_plan = serialize_query(query);
-    let placement = prost_types::Any {
-        type_url: "type.googleapis.com/google.protobuf.StringValue".to_string(),
-        value: placement.to_string().bytes().collect::<Vec<u8>>(),
-    };
-    let mut context = HashMap::new();
-    context.insert("placement".to_string(), placement);
+    let mut entries = context.clone();
+    entries.extend(placement.context_entries());
+    let context = entries
+        .into_iter()
+        .map(|(key, value)| (key, string_value(value)))
+        .collect();
     let request = SubmitQueryRequest {
         query_plan: Some(query_plan),
         context,
@@ -43,3 +53,10 @@ pub fn serialize_request(query: &Query, placement: PlacementStrategy) -> Vec<u8>
     };
     request.encode_to_vec()
 }
+
+fn string_value(value: String) -> prost_types::Any {
+    prost_types::Any {
+        type_url: "type.googleapis.com/google.protobuf.StringValue".to_string(),
+        value: value.into_bytes(),
+    }
+}
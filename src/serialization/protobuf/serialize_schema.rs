@@ -0,0 +1,93 @@
+use prost::Message;
+
+use crate::query::schema::Schema;
+
+use super::{
+    deserialize_error::DeserializeError,
+    nes::{
+        serializable_schema::MemoryLayoutType, SerializableField, SerializableNamedSchema,
+        SerializableSchema,
+    },
+    serialize_data_type::{deserialize_data_type, serialize_data_type},
+};
+
+pub fn serialize_schema(schema: &Schema) -> SerializableSchema {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| SerializableField {
+            name: field.name().to_string(),
+            r#type: Some(serialize_data_type(field.data_type())),
+        })
+        .collect();
+    SerializableSchema {
+        fields,
+        layout_type: MemoryLayoutType::RowLayout.into(),
+    }
+}
+
+pub fn deserialize_schema(schema: &SerializableSchema) -> Result<Schema, DeserializeError> {
+    let mut result = Schema::new();
+    for field in &schema.fields {
+        let Some(data_type) = &field.r#type else {
+            return Err(DeserializeError::MissingField(format!(
+                "type of field {}",
+                field.name
+            )));
+        };
+        result = result.with_field(&field.name, deserialize_data_type(data_type)?);
+    }
+    Ok(result)
+}
+
+/// Encodes a logical source with its schema as expected by the source catalog of the coordinator.
+pub fn serialize_named_schema(source_name: &str, schema: &Schema) -> Vec<u8> {
+    let named_schema = SerializableNamedSchema {
+        source_name: source_name.to_string(),
+        schema: Some(serialize_schema(schema)),
+    };
+    named_schema.encode_to_vec()
+}
+
+/// Decodes a logical source with its schema as sent to the source catalog of the coordinator.
+pub fn decode_named_schema(bytes: &[u8]) -> Result<(String, Schema), DeserializeError> {
+    let named_schema = SerializableNamedSchema::decode(bytes)?;
+    let Some(schema) = &named_schema.schema else {
+        return Err(DeserializeError::MissingField(format!(
+            "schema of {}",
+            named_schema.source_name
+        )));
+    };
+    Ok((named_schema.source_name, deserialize_schema(schema)?))
+}
+
+/// Encodes a schema as returned by the source catalog of the coordinator.
+pub fn encode_schema(schema: &Schema) -> Vec<u8> {
+    serialize_schema(schema).encode_to_vec()
+}
+
+/// Decodes a `SerializableSchema` message as returned by the source catalog of the coordinator.
+pub fn decode_schema(bytes: &[u8]) -> Result<Schema, DeserializeError> {
+    let schema = SerializableSchema::decode(bytes)?;
+    deserialize_schema(&schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use nes_types::{FloatType, IntType, NesType};
+    use prost::Message;
+
+    use super::{decode_schema, serialize_schema};
+    use crate::query::schema::Schema;
+
+    #[test]
+    fn test_schema_round_trip() {
+        let schema = Schema::new()
+            .with_field("id", NesType::Int(IntType::Unsigned64))
+            .with_field("value", NesType::Int(IntType::Signed32))
+            .with_field("temperature", NesType::Float(FloatType::Bit64))
+            .with_field("valid", NesType::Bool);
+        let bytes = serialize_schema(&schema).encode_to_vec();
+        assert_eq!(schema, decode_schema(&bytes).unwrap());
+    }
+}
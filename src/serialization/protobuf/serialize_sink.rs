@@ -1,11 +1,14 @@
 use prost_types::Any;
 
-use crate::query::sink::Sink;
+use crate::query::sink::{MqttQualityOfService, MqttTimeUnit, OpcIdentifierType, Sink};
 
 use super::nes::serializable_operator::{
     sink_details::{
-        SerializableFileSinkDescriptor, SerializableNullOutputSinkDescriptor,
-        SerializablePrintSinkDescriptor,
+        serializable_mqtt_sink_descriptor::{ServiceQualities, TimeUnits},
+        SerializableFileSinkDescriptor, SerializableKafkaSinkDescriptor,
+        SerializableMqttSinkDescriptor, SerializableNullOutputSinkDescriptor,
+        SerializableOpcSinkDescriptor, SerializablePrintSinkDescriptor,
+        SerializableZmqSinkDescriptor,
     },
     SinkDetails,
 };
@@ -19,11 +22,100 @@ pub fn serialize_sink_details(sink: &Sink) -> prost_types::Any {
             path,
             format,
             append,
-        } => file_sink_details(path.to_string(), format.to_string(), *append),
+            add_timestamp,
+        } => file_sink_details(
+            path.to_string(),
+            format.as_coordinator_str().to_string(),
+            *append,
+            *add_timestamp,
+        ),
+        Sink::Kafka {
+            brokers,
+            topic,
+            connect_timeout,
+        } => sink_details(&SerializableKafkaSinkDescriptor {
+            topic: topic.clone(),
+            brokers: brokers.clone(),
+            kafka_connect_timeout: *connect_timeout,
+        }),
+        Sink::Mqtt {
+            address,
+            client_id,
+            topic,
+            user,
+            max_buffered_messages,
+            time_unit,
+            message_delay,
+            quality_of_service,
+            asynchronous_client,
+        } => sink_details(&SerializableMqttSinkDescriptor {
+            address: address.clone(),
+            client_id: client_id.clone(),
+            topic: topic.clone(),
+            user: user.clone(),
+            max_buffered_ms_gs: *max_buffered_messages,
+            time_unit: serialize_time_unit(*time_unit) as i32,
+            msg_delay: *message_delay,
+            quality_of_service: serialize_service_quality(*quality_of_service) as i32,
+            asynchronous_client: *asynchronous_client,
+        }),
+        Sink::Zmq { host, port } => sink_details(&SerializableZmqSinkDescriptor {
+            host: host.clone(),
+            port: *port,
+            is_internal: false,
+        }),
+        Sink::Opc {
+            url,
+            namespace_index,
+            identifier,
+            identifier_type,
+            user,
+            password,
+        } => sink_details(&SerializableOpcSinkDescriptor {
+            url: url.clone(),
+            namespace_index: *namespace_index,
+            identifier: identifier.clone(),
+            identifier_type: serialize_identifier_type(*identifier_type),
+            user: user.clone(),
+            password: password.expose().to_string(),
+        }),
     };
     Any::from_msg(&details).unwrap()
 }
 
+fn sink_details<M: prost::Name>(descriptor: &M) -> SinkDetails {
+    SinkDetails {
+        sink_descriptor: Some(Any::from_msg(descriptor).unwrap()),
+        ..Default::default()
+    }
+}
+
+fn serialize_time_unit(time_unit: MqttTimeUnit) -> TimeUnits {
+    match time_unit {
+        MqttTimeUnit::Nanoseconds => TimeUnits::Nanoseconds,
+        MqttTimeUnit::Milliseconds => TimeUnits::Milliseconds,
+        MqttTimeUnit::Seconds => TimeUnits::Seconds,
+    }
+}
+
+fn serialize_service_quality(quality_of_service: MqttQualityOfService) -> ServiceQualities {
+    match quality_of_service {
+        MqttQualityOfService::AtMostOnce => ServiceQualities::AtMostOnce,
+        MqttQualityOfService::AtLeastOnce => ServiceQualities::AtLeastOnce,
+        MqttQualityOfService::ExactlyOnce => ServiceQualities::ExactlyOnce,
+    }
+}
+
+/// Returns the `UA_NodeIdType` of open62541 that the coordinator expects.
+fn serialize_identifier_type(identifier_type: OpcIdentifierType) -> u32 {
+    match identifier_type {
+        OpcIdentifierType::Numeric => 0,
+        OpcIdentifierType::String => 3,
+        OpcIdentifierType::Guid => 4,
+        OpcIdentifierType::ByteString => 5,
+    }
+}
+
 fn null_sink_details() -> SinkDetails {
     let descriptor = SerializableNullOutputSinkDescriptor {};
     let descriptor = Any::from_msg(&descriptor).unwrap();
@@ -42,12 +134,17 @@ fn print_sink_details() -> SinkDetails {
     }
 }
 
-fn file_sink_details(file_path: String, sink_format: String, append: bool) -> SinkDetails {
+fn file_sink_details(
+    file_path: String,
+    sink_format: String,
+    append: bool,
+    add_timestamp: bool,
+) -> SinkDetails {
     let descriptor = SerializableFileSinkDescriptor {
         file_path,
         sink_format,
         append,
-        add_timestamp: false,
+        add_timestamp,
     };
     let descriptor = Any::from_msg(&descriptor).unwrap();
     SinkDetails {
@@ -0,0 +1,226 @@
+use prost_types::Any;
+
+use crate::query::{
+    schema::Schema,
+    source::{InlineSource, InputFormat, SourceDescriptor},
+};
+
+use super::{
+    nes::{
+        serializable_operator::{
+            source_details::{
+                SerializableArrowSourceDescriptor, SerializableBinarySourceDescriptor,
+                SerializableCsvSourceDescriptor, SerializableDefaultSourceDescriptor,
+                SerializableKafkaSourceDescriptor, SerializableLogicalSourceDescriptor,
+                SerializableMqttSourceDescriptor, SerializableTcpSourceDescriptor,
+                SerializableZmqSourceDescriptor,
+            },
+            SourceDetails,
+        },
+        serializable_physical_source_type::{
+            InputFormat as SerializableInputFormat, SerializableArrowSourceType,
+            SerializableCsvSourceType, SerializableMqttSourceType, SerializableTcpSourceType,
+            TcpDecideMessageSize,
+        },
+        SerializablePhysicalSourceType,
+    },
+    serialize_schema::serialize_schema,
+};
+
+// Socket options of TCP sources as defined by the C socket API.
+const AF_INET: u32 = 2;
+const SOCK_STREAM: u32 = 1;
+
+pub fn logical_source_details(
+    source_name: &str,
+    physical_source_name: Option<&str>,
+) -> SourceDetails {
+    let descriptor = SerializableLogicalSourceDescriptor {
+        logical_source_name: source_name.to_string(),
+        physical_source_name: physical_source_name.unwrap_or_default().to_string(),
+        ..Default::default()
+    };
+    source_details(&descriptor)
+}
+
+pub fn inline_source_details(source: &InlineSource) -> SourceDetails {
+    let source_schema = Some(serialize_schema(&qualified_schema(source)));
+    match &source.descriptor {
+        SourceDescriptor::Csv {
+            path,
+            skip_header,
+            delimiter,
+            number_of_buffers_to_produce,
+            number_of_tuples_to_produce_per_buffer,
+            gathering_interval,
+        } => {
+            let source_type = SerializableCsvSourceType {
+                file_path: path.clone(),
+                skip_header: *skip_header,
+                delimiter: delimiter.clone(),
+                number_of_buffers_to_produce: *number_of_buffers_to_produce,
+                number_of_tuples_to_produce_per_buffer: *number_of_tuples_to_produce_per_buffer,
+                source_gathering_interval: *gathering_interval,
+            };
+            source_details(&SerializableCsvSourceDescriptor {
+                physical_source_type: Some(physical_source_type(
+                    "CSV_SOURCE",
+                    source,
+                    &source_type,
+                )),
+                source_schema,
+            })
+        }
+        SourceDescriptor::Binary { path } => source_details(&SerializableBinarySourceDescriptor {
+            file_path: path.clone(),
+            source_schema,
+        }),
+        SourceDescriptor::Default {
+            number_of_buffers_to_produce,
+            gathering_interval,
+        } => source_details(&SerializableDefaultSourceDescriptor {
+            number_of_buffers_to_produce: *number_of_buffers_to_produce,
+            source_gathering_interval: *gathering_interval,
+            source_schema,
+        }),
+        SourceDescriptor::Kafka {
+            brokers,
+            topic,
+            group_id,
+            auto_commit,
+            connect_timeout,
+        } => source_details(&SerializableKafkaSourceDescriptor {
+            brokers: brokers.clone(),
+            topic: topic.clone(),
+            group_id: group_id.clone(),
+            auto_commit: *auto_commit,
+            kafka_connect_timeout: *connect_timeout,
+            source_schema,
+        }),
+        SourceDescriptor::Mqtt {
+            url,
+            client_id,
+            user,
+            topic,
+            quality_of_service,
+            clean_session,
+            flush_interval_ms,
+            input_format,
+        } => {
+            let source_type = SerializableMqttSourceType {
+                url: url.clone(),
+                client_id: client_id.clone(),
+                user_name: user.clone(),
+                topic: topic.clone(),
+                qos: *quality_of_service,
+                clean_session: *clean_session,
+                flush_interval_ms: *flush_interval_ms,
+                input_format: serialize_input_format(*input_format) as i32,
+            };
+            source_details(&SerializableMqttSourceDescriptor {
+                physical_source_type: Some(physical_source_type(
+                    "MQTT_SOURCE",
+                    source,
+                    &source_type,
+                )),
+                source_schema,
+            })
+        }
+        SourceDescriptor::Tcp {
+            host,
+            port,
+            input_format,
+            tuple_separator,
+            flush_interval_ms,
+        } => {
+            let source_type = SerializableTcpSourceType {
+                socket_host: host.clone(),
+                socket_port: *port,
+                socket_domain: AF_INET,
+                socket_type: SOCK_STREAM,
+                flush_interval_ms: *flush_interval_ms,
+                input_format: serialize_input_format(*input_format) as i32,
+                tcp_decide_message_size: TcpDecideMessageSize::TupleSeparator as i32,
+                tuple_separator: tuple_separator.clone(),
+                ..Default::default()
+            };
+            source_details(&SerializableTcpSourceDescriptor {
+                physical_source_type: Some(physical_source_type(
+                    "TCP_SOURCE",
+                    source,
+                    &source_type,
+                )),
+                source_schema,
+            })
+        }
+        SourceDescriptor::Zmq { host, port } => source_details(&SerializableZmqSourceDescriptor {
+            host: host.clone(),
+            port: *port,
+            source_schema,
+        }),
+        SourceDescriptor::Arrow {
+            path,
+            number_of_buffers_to_produce,
+            number_of_tuples_to_produce_per_buffer,
+            gathering_interval,
+        } => {
+            let source_type = SerializableArrowSourceType {
+                file_path: path.clone(),
+                number_of_buffers_to_produce: *number_of_buffers_to_produce,
+                number_of_tuples_to_produce_per_buffer: *number_of_tuples_to_produce_per_buffer,
+                source_gathering_interval: *gathering_interval,
+            };
+            source_details(&SerializableArrowSourceDescriptor {
+                physical_source_type: Some(physical_source_type(
+                    "ARROW_SOURCE",
+                    source,
+                    &source_type,
+                )),
+                source_schema,
+            })
+        }
+    }
+}
+
+fn source_details<M: prost::Name>(descriptor: &M) -> SourceDetails {
+    SourceDetails {
+        source_descriptor: Some(Any::from_msg(descriptor).unwrap()),
+        ..Default::default()
+    }
+}
+
+fn physical_source_type<M: prost::Name>(
+    source_type: &str,
+    source: &InlineSource,
+    specific_type: &M,
+) -> SerializablePhysicalSourceType {
+    SerializablePhysicalSourceType {
+        source_type: source_type.to_string(),
+        logical_source_name: source.name.clone(),
+        physical_source_name: source.name.clone(),
+        specific_physical_source_type: Some(Any::from_msg(specific_type).unwrap()),
+    }
+}
+
+fn serialize_input_format(input_format: InputFormat) -> SerializableInputFormat {
+    match input_format {
+        InputFormat::Json => SerializableInputFormat::Json,
+        InputFormat::Csv => SerializableInputFormat::Csv,
+        InputFormat::NesBinary => SerializableInputFormat::NesBinary,
+    }
+}
+
+/// Qualifies the fields of the schema with the name of the source like the coordinator does for
+/// registered sources, e.g. `id` becomes `orders$id`.
+fn qualified_schema(source: &InlineSource) -> Schema {
+    let mut schema = Schema::new();
+    for field in source.schema.fields() {
+        let name = if field.name().contains('$') {
+            field.name().to_string()
+        } else {
+            format!("{}${}", source.name, field.name())
+        };
+        schema = schema.with_field(name, field.data_type());
+    }
+    schema
+}
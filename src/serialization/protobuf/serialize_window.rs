@@ -1,6 +1,6 @@
 use crate::expression::{expression::RawExpr, field::Field};
 use crate::query::{
-    time::TimeCharacteristic,
+    time::{TimeCharacteristic, TimeUnit, WatermarkStrategy},
     window::{
         aggregation::{Aggregation, AggregationType},
         window_descriptor::WindowDescriptor,
@@ -13,7 +13,12 @@ use super::{
         serializable_operator::{
             time_characteristic,
             window_details::{aggregation::Type, Aggregation as SerializableAggregation},
-            TimeCharacteristic as STimeCharacter, TumblingWindow,
+            watermark_strategy_details::{
+                SerializableEventTimeWatermarkStrategyDescriptor,
+                SerializableIngestionTimeWatermarkStrategyDescriptor,
+            },
+            SlidingWindow, ThresholdWindow, TimeCharacteristic as STimeCharacter, TumblingWindow,
+            WatermarkStrategyDetails,
         },
         SerializableExpression,
     },
@@ -29,18 +34,60 @@ pub fn serialize_window_descriptor(descriptor: &WindowDescriptor) -> Any {
             time_characteristic: Some(serialize_time_characteristic(time_character)),
             size: duration.to_milliseconds(),
         }),
+        WindowDescriptor::SlidingWindow {
+            size,
+            slide,
+            time_character,
+        } => Any::from_msg(&SlidingWindow {
+            time_characteristic: Some(serialize_time_characteristic(time_character)),
+            size: size.to_milliseconds(),
+            slide: slide.to_milliseconds(),
+        }),
+        WindowDescriptor::ThresholdWindow {
+            predicate,
+            minimum_count,
+        } => Any::from_msg(&ThresholdWindow {
+            predicate: Some(serialize_expression(&predicate.0)),
+            minimum_count: *minimum_count,
+        }),
     }
     .unwrap()
 }
 
 pub fn serialize_time_characteristic(time_character: &TimeCharacteristic) -> STimeCharacter {
-    let TimeCharacteristic::EventTime { field_name, unit } = time_character else {
-        unimplemented!();
+    match time_character {
+        TimeCharacteristic::EventTime { field_name, unit } => STimeCharacter {
+            r#type: time_characteristic::Type::EventTime.into(),
+            field: field_name.to_string(),
+            multiplier: unit.to_scalar().into(),
+        },
+        TimeCharacteristic::IngestionTime => STimeCharacter {
+            r#type: time_characteristic::Type::IngestionTime.into(),
+            field: String::new(),
+            multiplier: TimeUnit::Milliseconds.to_scalar().into(),
+        },
+    }
+}
+
+/// The allowed lateness is serialized in milliseconds, the multiplier converts the timestamps of
+/// the field to milliseconds.
+pub fn serialize_watermark_strategy(strategy: &WatermarkStrategy) -> WatermarkStrategyDetails {
+    let strategy = match strategy {
+        WatermarkStrategy::EventTime {
+            field_name,
+            allowed_lateness,
+            unit,
+        } => Any::from_msg(&SerializableEventTimeWatermarkStrategyDescriptor {
+            on_field: Some(serialize_field(&Field::untyped(field_name))),
+            allowed_lateness: allowed_lateness.to_milliseconds().into(),
+            multiplier: unit.to_scalar().into(),
+        }),
+        WatermarkStrategy::IngestionTime => {
+            Any::from_msg(&SerializableIngestionTimeWatermarkStrategyDescriptor {})
+        }
     };
-    STimeCharacter {
-        r#type: time_characteristic::Type::EventTime.into(),
-        field: field_name.to_string(),
-        multiplier: unit.to_scalar().into(),
+    WatermarkStrategyDetails {
+        strategy: Some(strategy.unwrap()),
     }
 }
 
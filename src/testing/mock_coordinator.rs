@@ -0,0 +1,728 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    body::Bytes,
+    extract::{Query as UrlQuery, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use crate::{
+    query::{operator::Operator, schema::Schema, stringify::stringify_query, Query},
+    runtime::{
+        nebula_stream_config::{NebulaStreamConfig, NebulaStreamConfigBuilder, RetryPolicy},
+        nebula_stream_runtime::{format_schema, NebulaStreamRuntime},
+        query_state::QueryState,
+    },
+    serialization::protobuf::{
+        deserialize_query::decode_request,
+        serialize_schema::{decode_named_schema, encode_schema},
+    },
+};
+
+/// The REST endpoints of the coordinator that are implemented by the `MockCoordinator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Connectivity,
+    ExecuteQuery,
+    RegisteredQueries,
+    QueryStatus,
+    QueryPlan,
+    ExecutionPlan,
+    OptimizationPhases,
+    StopQuery,
+    LogicalSources,
+    SourceSchema,
+    AddLogicalSource,
+    UpdateLogicalSource,
+    DeleteLogicalSource,
+}
+
+/// A failure the `MockCoordinator` responds with instead of handling a request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Failure {
+    /// Responds with `status` and `body` as is.
+    Status { status: u16, body: String },
+    /// Responds with an internal server error and a JSON body containing `message`, like the
+    /// coordinator does for failed requests.
+    Message(String),
+}
+
+struct MockQuery {
+    query: Query,
+    context: HashMap<String, String>,
+    state: QueryState,
+    script: VecDeque<QueryState>,
+}
+
+impl MockQuery {
+    fn json(&self, query_id: i64) -> Value {
+        json!({
+            "queryId": query_id,
+            "queryStatus": self.state.as_coordinator_str(),
+            "queryString": stringify_query(&self.query),
+        })
+    }
+}
+
+struct Catalog {
+    next_query_id: i64,
+    queries: BTreeMap<i64, MockQuery>,
+    sources: BTreeMap<String, Schema>,
+    deploy_script: Vec<QueryState>,
+    stop_script: Vec<QueryState>,
+    failures: HashMap<Endpoint, VecDeque<Failure>>,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self {
+            next_query_id: 1,
+            queries: BTreeMap::new(),
+            sources: BTreeMap::new(),
+            deploy_script: vec![
+                QueryState::Registered,
+                QueryState::Optimizing,
+                QueryState::Deployed,
+                QueryState::Running,
+            ],
+            stop_script: vec![QueryState::MarkedForHardStop, QueryState::Stopped],
+            failures: HashMap::new(),
+        }
+    }
+}
+
+type SharedCatalog = Arc<Mutex<Catalog>>;
+
+/// An in-process HTTP server that implements the REST endpoints of the coordinator used by the
+/// `NebulaStreamRuntime`. Submitted queries are decoded and kept in a catalog. Every status
+/// request of a query returns its current state and then advances it by one state of its script.
+/// The server is shut down when the `MockCoordinator` is dropped.
+pub struct MockCoordinator {
+    address: SocketAddr,
+    catalog: SharedCatalog,
+    server: JoinHandle<std::io::Result<()>>,
+}
+
+impl MockCoordinator {
+    /// Starts the server on a free port of the loopback interface.
+    pub async fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let address = listener.local_addr()?;
+        let catalog = SharedCatalog::default();
+        let router = router(catalog.clone());
+        let server = tokio::spawn(async move { axum::serve(listener, router).await });
+        log::debug!("Started mock coordinator on {address}.");
+        Ok(Self {
+            address,
+            catalog,
+            server,
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Returns a runtime that is connected to this coordinator. Requests are not retried so that
+    /// injected failures reach the caller.
+    pub fn runtime(&self) -> NebulaStreamRuntime {
+        let config = self
+            .config_builder()
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .expect("The address of the mock coordinator is a valid URL");
+        NebulaStreamRuntime::with_config(config).expect("Unable to build the HTTP client")
+    }
+
+    /// Returns a config builder that points to this coordinator.
+    pub fn config_builder(&self) -> NebulaStreamConfigBuilder {
+        NebulaStreamConfig::builder().address(self.address)
+    }
+
+    /// Sets the states that queries submitted from now on go through. Defaults to registered,
+    /// optimizing, deployed and running.
+    pub fn set_deploy_script(&self, states: impl IntoIterator<Item = QueryState>) {
+        self.catalog().deploy_script = states.into_iter().collect();
+    }
+
+    /// Sets the states that queries go through once they are stopped. Defaults to marked for hard
+    /// stop and stopped.
+    pub fn set_stop_script(&self, states: impl IntoIterator<Item = QueryState>) {
+        self.catalog().stop_script = states.into_iter().collect();
+    }
+
+    /// Replaces the remaining states of a submitted query. Returns false if there is no query
+    /// with `query_id`.
+    pub fn script_query(
+        &self,
+        query_id: i64,
+        states: impl IntoIterator<Item = QueryState>,
+    ) -> bool {
+        let mut catalog = self.catalog();
+        let Some(query) = catalog.queries.get_mut(&query_id) else {
+            return false;
+        };
+        query.script = states.into_iter().collect();
+        true
+    }
+
+    /// Queues a failure for `endpoint`. Each queued failure answers a single request.
+    pub fn inject_failure(&self, endpoint: Endpoint, failure: Failure) {
+        self.catalog()
+            .failures
+            .entry(endpoint)
+            .or_default()
+            .push_back(failure);
+    }
+
+    pub fn add_logical_source(&self, source_name: impl Into<String>, schema: Schema) {
+        self.catalog().sources.insert(source_name.into(), schema);
+    }
+
+    /// Returns the query as decoded from the submitted request.
+    pub fn submitted_query(&self, query_id: i64) -> Option<Query> {
+        let catalog = self.catalog();
+        catalog
+            .queries
+            .get(&query_id)
+            .map(|query| query.query.clone())
+    }
+
+    /// Returns the placement strategy of the submitted request.
+    pub fn placement(&self, query_id: i64) -> Option<String> {
+        self.context(query_id)?.remove("placement")
+    }
+
+    /// Returns the context of the submitted request.
+    pub fn context(&self, query_id: i64) -> Option<HashMap<String, String>> {
+        let catalog = self.catalog();
+        Some(catalog.queries.get(&query_id)?.context.clone())
+    }
+
+    /// Returns the current state of a query without advancing it.
+    pub fn query_state(&self, query_id: i64) -> Option<QueryState> {
+        let catalog = self.catalog();
+        catalog.queries.get(&query_id).map(|query| query.state)
+    }
+
+    fn catalog(&self) -> std::sync::MutexGuard<'_, Catalog> {
+        self.catalog
+            .lock()
+            .expect("Mock coordinator catalog should not be poisoned!")
+    }
+}
+
+impl Drop for MockCoordinator {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryIdParam {
+    query_id: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourceNameParam {
+    logical_source_name: String,
+}
+
+fn router(catalog: SharedCatalog) -> Router {
+    Router::new()
+        .route("/v1/nes/connectivity/check", get(connectivity))
+        .route("/v1/nes/query/execute-query-ex", post(execute_query))
+        .route("/v1/nes/query/stop-query", delete(stop_query))
+        .route("/v1/nes/query/query-plan", get(query_plan))
+        .route("/v1/nes/query/execution-plan", get(execution_plan))
+        .route("/v1/nes/query/optimization-phase", get(optimization_phases))
+        .route(
+            "/v1/nes/queryCatalog/allRegisteredQueries",
+            get(registered_queries),
+        )
+        .route("/v1/nes/queryCatalog/status", get(query_status))
+        .route(
+            "/v1/nes/sourceCatalog/allLogicalSource",
+            get(logical_sources),
+        )
+        .route("/v1/nes/sourceCatalog/schema", get(source_schema))
+        .route(
+            "/v1/nes/sourceCatalog/addLogicalSource-ex",
+            post(add_logical_source),
+        )
+        .route(
+            "/v1/nes/sourceCatalog/updateLogicalSource-ex",
+            post(update_logical_source),
+        )
+        .route(
+            "/v1/nes/sourceCatalog/deleteLogicalSource",
+            delete(delete_logical_source),
+        )
+        .with_state(catalog)
+}
+
+/// Answers a request to `endpoint` with the next injected failure or else with `handler`.
+fn handle(
+    catalog: &SharedCatalog,
+    endpoint: Endpoint,
+    handler: impl FnOnce(&mut Catalog) -> Response,
+) -> Response {
+    let mut catalog = catalog
+        .lock()
+        .expect("Mock coordinator catalog should not be poisoned!");
+    let failure = catalog
+        .failures
+        .get_mut(&endpoint)
+        .and_then(VecDeque::pop_front);
+    match failure {
+        Some(Failure::Status { status, body }) => {
+            let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            (status, body).into_response()
+        }
+        Some(Failure::Message(message)) => error(StatusCode::INTERNAL_SERVER_ERROR, message),
+        None => handler(&mut catalog),
+    }
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(json!({ "message": message.into() }))).into_response()
+}
+
+fn unknown_query(query_id: i64) -> Response {
+    error(
+        StatusCode::NOT_FOUND,
+        format!("No query with id {query_id} exists."),
+    )
+}
+
+fn unknown_source(source_name: &str) -> Response {
+    error(
+        StatusCode::NOT_FOUND,
+        format!("No logical source with name {source_name} exists."),
+    )
+}
+
+async fn connectivity(State(catalog): State<SharedCatalog>) -> Response {
+    handle(&catalog, Endpoint::Connectivity, |_| {
+        Json(json!({ "statusCode": 200, "success": true })).into_response()
+    })
+}
+
+async fn execute_query(State(catalog): State<SharedCatalog>, body: Bytes) -> Response {
+    handle(&catalog, Endpoint::ExecuteQuery, |catalog| {
+        let (query, context) = match decode_request(&body) {
+            Ok(request) => request,
+            Err(err) => return error(StatusCode::BAD_REQUEST, err.to_string()),
+        };
+        let query_id = catalog.next_query_id;
+        catalog.next_query_id += 1;
+        let mut script: VecDeque<_> = catalog.deploy_script.iter().copied().collect();
+        let state = script.pop_front().unwrap_or(QueryState::Registered);
+        log::debug!("Mock coordinator registered query with id {query_id}.");
+        catalog.queries.insert(
+            query_id,
+            MockQuery {
+                query,
+                context,
+                state,
+                script,
+            },
+        );
+        Json(json!({ "queryId": query_id })).into_response()
+    })
+}
+
+async fn stop_query(
+    State(catalog): State<SharedCatalog>,
+    UrlQuery(param): UrlQuery<QueryIdParam>,
+) -> Response {
+    handle(&catalog, Endpoint::StopQuery, |catalog| {
+        let mut script: VecDeque<_> = catalog.stop_script.iter().copied().collect();
+        let Some(query) = catalog.queries.get_mut(&param.query_id) else {
+            return unknown_query(param.query_id);
+        };
+        if query.state.is_terminal() {
+            return error(
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Query with id {} is already {}.",
+                    param.query_id, query.state
+                ),
+            );
+        }
+        query.state = script.pop_front().unwrap_or(QueryState::Stopped);
+        query.script = script;
+        Json(json!({ "success": true })).into_response()
+    })
+}
+
+async fn registered_queries(State(catalog): State<SharedCatalog>) -> Response {
+    handle(&catalog, Endpoint::RegisteredQueries, |catalog| {
+        let queries: Vec<_> = catalog
+            .queries
+            .iter()
+            .map(|(query_id, query)| query.json(*query_id))
+            .collect();
+        Json(Value::Array(queries)).into_response()
+    })
+}
+
+async fn query_status(
+    State(catalog): State<SharedCatalog>,
+    UrlQuery(param): UrlQuery<QueryIdParam>,
+) -> Response {
+    handle(&catalog, Endpoint::QueryStatus, |catalog| {
+        let Some(query) = catalog.queries.get_mut(&param.query_id) else {
+            return unknown_query(param.query_id);
+        };
+        let response = Json(query.json(param.query_id)).into_response();
+        if let Some(state) = query.script.pop_front() {
+            query.state = state;
+        }
+        response
+    })
+}
+
+async fn query_plan(
+    State(catalog): State<SharedCatalog>,
+    UrlQuery(param): UrlQuery<QueryIdParam>,
+) -> Response {
+    handle(&catalog, Endpoint::QueryPlan, |catalog| {
+        let Some(query) = catalog.queries.get(&param.query_id) else {
+            return unknown_query(param.query_id);
+        };
+        Json(plan_graph(&query.query)).into_response()
+    })
+}
+
+async fn execution_plan(
+    State(catalog): State<SharedCatalog>,
+    UrlQuery(param): UrlQuery<QueryIdParam>,
+) -> Response {
+    handle(&catalog, Endpoint::ExecutionPlan, |catalog| {
+        let Some(query) = catalog.queries.get(&param.query_id) else {
+            return unknown_query(param.query_id);
+        };
+        Json(json!({
+            "executionNodes": [{
+                "executionNodeId": 1,
+                "topologyNodeId": 1,
+                "topologyNodeIpAddress": "127.0.0.1",
+                "ScheduledDecomposedQueries": [{
+                    "decomposedQueryId": 1,
+                    "decomposedQueryPlan": stringify_query(&query.query),
+                }],
+            }],
+        }))
+        .into_response()
+    })
+}
+
+async fn optimization_phases(
+    State(catalog): State<SharedCatalog>,
+    UrlQuery(param): UrlQuery<QueryIdParam>,
+) -> Response {
+    handle(&catalog, Endpoint::OptimizationPhases, |catalog| {
+        let Some(query) = catalog.queries.get(&param.query_id) else {
+            return unknown_query(param.query_id);
+        };
+        Json(json!({ "OriginalQueryPlan": stringify_query(&query.query) })).into_response()
+    })
+}
+
+async fn logical_sources(State(catalog): State<SharedCatalog>) -> Response {
+    handle(&catalog, Endpoint::LogicalSources, |catalog| {
+        let sources: Vec<_> = catalog
+            .sources
+            .iter()
+            .map(|(name, schema)| json!({ name: format_schema(schema) }))
+            .collect();
+        Json(Value::Array(sources)).into_response()
+    })
+}
+
+async fn source_schema(
+    State(catalog): State<SharedCatalog>,
+    UrlQuery(param): UrlQuery<SourceNameParam>,
+) -> Response {
+    handle(&catalog, Endpoint::SourceSchema, |catalog| {
+        match catalog.sources.get(&param.logical_source_name) {
+            Some(schema) => encode_schema(schema).into_response(),
+            None => unknown_source(&param.logical_source_name),
+        }
+    })
+}
+
+async fn add_logical_source(State(catalog): State<SharedCatalog>, body: Bytes) -> Response {
+    handle(&catalog, Endpoint::AddLogicalSource, |catalog| {
+        let (source_name, schema) = match decode_named_schema(&body) {
+            Ok(named_schema) => named_schema,
+            Err(err) => return error(StatusCode::BAD_REQUEST, err.to_string()),
+        };
+        if catalog.sources.contains_key(&source_name) {
+            return error(
+                StatusCode::BAD_REQUEST,
+                format!("Logical source {source_name} already exists."),
+            );
+        }
+        catalog.sources.insert(source_name, schema);
+        Json(json!({ "success": true })).into_response()
+    })
+}
+
+async fn update_logical_source(State(catalog): State<SharedCatalog>, body: Bytes) -> Response {
+    handle(&catalog, Endpoint::UpdateLogicalSource, |catalog| {
+        let (source_name, schema) = match decode_named_schema(&body) {
+            Ok(named_schema) => named_schema,
+            Err(err) => return error(StatusCode::BAD_REQUEST, err.to_string()),
+        };
+        let Some(existing) = catalog.sources.get_mut(&source_name) else {
+            return unknown_source(&source_name);
+        };
+        *existing = schema;
+        Json(json!({ "success": true })).into_response()
+    })
+}
+
+async fn delete_logical_source(
+    State(catalog): State<SharedCatalog>,
+    UrlQuery(param): UrlQuery<SourceNameParam>,
+) -> Response {
+    handle(&catalog, Endpoint::DeleteLogicalSource, |catalog| {
+        if catalog.sources.remove(&param.logical_source_name).is_none() {
+            return unknown_source(&param.logical_source_name);
+        }
+        Json(json!({ "success": true })).into_response()
+    })
+}
+
+/// Builds the operator graph of a query in the format of the query plan endpoint.
+fn plan_graph(query: &Query) -> Value {
+    let mut nodes = vec![json!({
+        "id": 1,
+        "title": format!("SINK({})", query.sink()),
+        "nodeType": "SINK",
+    })];
+    let mut edges = Vec::new();
+    let mut pending = vec![(query.operator(), 1)];
+    while let Some((operator, parent_id)) = pending.pop() {
+        let id = nodes.len() + 1;
+        let node_type = if operator.has_child() {
+            "OPERATOR"
+        } else {
+            "SOURCE"
+        };
+        nodes.push(json!({ "id": id, "title": operator.to_string(), "nodeType": node_type }));
+        edges.push(json!({ "source": id, "target": parent_id }));
+        pending.extend(operator.child().map(|child| (child, id)));
+        match operator {
+            Operator::Union(union) => pending.push((&union.operators, id)),
+            Operator::Join(join) => pending.push((&join.joined_operators, id)),
+            _ => {}
+        }
+    }
+    json!({ "nodes": nodes, "edges": edges })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use nes_types::{IntType, NesType};
+
+    use super::{Endpoint, Failure, MockCoordinator};
+    use crate::{
+        prelude::{ExprBuilder as EB, *},
+        query::stringify::stringify_query,
+        runtime::nebula_stream_runtime::LogicalSource,
+    };
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+    const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+    fn test_query() -> Query {
+        QueryBuilder::from_source("test")
+            .filter(
+                EB::field("value")
+                    .greater_than(EB::literal(0))
+                    .build_logical()
+                    .unwrap(),
+            )
+            .sink(Sink::Print)
+    }
+
+    #[tokio::test]
+    async fn test_submit_status_stop() {
+        let coordinator = MockCoordinator::start().await.unwrap();
+        let runtime = coordinator.runtime();
+        assert!(runtime.check_connection().await);
+
+        let query = test_query();
+        let query_id = runtime
+            .execute_query(&query, PlacementStrategy::BottomUp)
+            .await
+            .unwrap();
+        let submitted = coordinator.submitted_query(query_id).unwrap();
+        assert_eq!(stringify_query(&query), stringify_query(&submitted));
+        assert_eq!(
+            Some("BottomUp".to_string()),
+            coordinator.placement(query_id)
+        );
+
+        let history = runtime
+            .wait_for_running(query_id, TIMEOUT, POLL_INTERVAL)
+            .await
+            .unwrap();
+        let expected = [
+            QueryState::Registered,
+            QueryState::Optimizing,
+            QueryState::Deployed,
+            QueryState::Running,
+        ];
+        assert_eq!(expected.to_vec(), history);
+        let queries = runtime.registered_queries().await.unwrap();
+        assert_eq!(1, queries.len());
+        assert_eq!(QueryState::Running, queries[0].query_status);
+
+        runtime.stop_query(query_id).await.unwrap();
+        let history = runtime
+            .wait_for_state(
+                query_id,
+                |state| state == QueryState::Stopped,
+                TIMEOUT,
+                POLL_INTERVAL,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![QueryState::MarkedForHardStop, QueryState::Stopped],
+            history
+        );
+        assert!(runtime.stop_query(query_id).await.is_err());
+        assert_eq!(None, runtime.query_status(query_id + 1).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_failed_query() {
+        let coordinator = MockCoordinator::start().await.unwrap();
+        coordinator.set_deploy_script([QueryState::Registered, QueryState::Failed]);
+        let runtime = coordinator.runtime();
+        let query_id = runtime
+            .execute_query(&test_query(), PlacementStrategy::BottomUp)
+            .await
+            .unwrap();
+        let result = runtime
+            .wait_for_running(query_id, TIMEOUT, POLL_INTERVAL)
+            .await;
+        assert!(matches!(
+            result,
+            Err(RuntimeError::UnexpectedQueryState { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_injected_failures() {
+        let coordinator = MockCoordinator::start().await.unwrap();
+        let runtime = coordinator.runtime();
+        coordinator.inject_failure(
+            Endpoint::ExecuteQuery,
+            Failure::Message("Unknown logical source".to_string()),
+        );
+        coordinator.inject_failure(
+            Endpoint::QueryStatus,
+            Failure::Status {
+                status: 503,
+                body: "unavailable".to_string(),
+            },
+        );
+        let result = runtime
+            .execute_query(&test_query(), PlacementStrategy::BottomUp)
+            .await;
+        assert!(matches!(result, Err(RuntimeError::Coordinator { .. })));
+        let query_id = runtime
+            .execute_query(&test_query(), PlacementStrategy::BottomUp)
+            .await
+            .unwrap();
+        let result = runtime.query_status(query_id).await;
+        assert!(matches!(result, Err(RuntimeError::HttpStatus { .. })));
+        assert_eq!(
+            Some(QueryState::Registered),
+            runtime.query_status(query_id).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retried_requests() {
+        let coordinator = MockCoordinator::start().await.unwrap();
+        let config = coordinator
+            .config_builder()
+            .retry_policy(RetryPolicy::exponential(2, Duration::from_millis(1)))
+            .build()
+            .unwrap();
+        let runtime = NebulaStreamRuntime::with_config(config).unwrap();
+        let query_id = runtime
+            .execute_query(&test_query(), PlacementStrategy::BottomUp)
+            .await
+            .unwrap();
+        let unavailable = || Failure::Status {
+            status: 503,
+            body: "unavailable".to_string(),
+        };
+        coordinator.inject_failure(Endpoint::QueryStatus, unavailable());
+        coordinator.inject_failure(Endpoint::QueryStatus, unavailable());
+        assert_eq!(
+            Some(QueryState::Registered),
+            runtime.query_status(query_id).await.unwrap()
+        );
+        for _ in 0..3 {
+            coordinator.inject_failure(Endpoint::QueryStatus, unavailable());
+        }
+        let result = runtime.query_status(query_id).await;
+        assert!(matches!(result, Err(RuntimeError::HttpStatus { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_logical_sources() {
+        let coordinator = MockCoordinator::start().await.unwrap();
+        let runtime = coordinator.runtime();
+        let schema = Schema::new()
+            .with_field("id", NesType::Int(IntType::Unsigned64))
+            .with_field("value", NesType::Int(IntType::Signed32));
+        runtime.add_logical_source("test", &schema).await.unwrap();
+        assert!(runtime.add_logical_source("test", &schema).await.is_err());
+        assert_eq!(
+            vec!["test".to_string()],
+            runtime.logical_sources().await.unwrap()
+        );
+        assert_eq!(schema, runtime.logical_source_schema("test").await.unwrap());
+        assert_eq!(
+            vec![LogicalSource {
+                name: "test".to_string(),
+                schema: schema.clone(),
+            }],
+            runtime.logical_sources_with_schema().await.unwrap()
+        );
+        runtime.delete_logical_source("test").await.unwrap();
+        assert!(runtime.logical_sources().await.unwrap().is_empty());
+        assert!(runtime.logical_source_schema("test").await.is_err());
+
+        // Names are URL encoded, `&` and `#` must not end the parameter.
+        let name = "a&b #1+c";
+        runtime.add_logical_source(name, &schema).await.unwrap();
+        assert_eq!(schema, runtime.logical_source_schema(name).await.unwrap());
+        runtime.delete_logical_source(name).await.unwrap();
+        assert!(runtime.logical_sources().await.unwrap().is_empty());
+    }
+}